@@ -0,0 +1,184 @@
+use block_insight_cross::transaction::transaction_filter::TransactionFilterContext;
+use criterion::{BenchmarkId, Criterion, black_box, criterion_group, criterion_main};
+use models::workspace::cheap_block_transaction::{
+    CheapBlockTransaction, ParsedVersionedTransactionWithStatusMeta,
+};
+use models::workspace::transaction_filter::log_message_filter::LogMessageFilter;
+use models::workspace::transaction_filter::program_id_filter::{
+    ProgramIdFilter, ProgramInvocationScope,
+};
+use models::workspace::transaction_filter::range_filter::{FeeRangeFilter, U64Range};
+use models::workspace::transaction_filter_expression::{
+    TransactionFilterExpression, TransactionFilterStatistics,
+};
+use serde_json::json;
+use solana_transaction_status_client_types::EncodedTransactionWithStatusMeta;
+use std::rc::Rc;
+
+const SWAP_PROGRAM: &str = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4";
+const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+const COMPUTE_BUDGET_PROGRAM: &str = "ComputeBudget111111111111111111111111111111";
+
+/// 构造一笔与主网swap交易结构相近的交易：两条计算预算指令、一条带CPI的swap指令，以及对应的日志
+fn fixture_transaction(i: usize) -> EncodedTransactionWithStatusMeta {
+    let failed = i % 7 == 0;
+    let fee = 5_000 + (i as u64 % 50) * 1_000;
+    let err = json!({"InstructionError": [2, {"Custom": 6001}]});
+    let (err, status) = if failed {
+        (err.clone(), json!({ "Err": err }))
+    } else {
+        (json!(null), json!({ "Ok": null }))
+    };
+    let value = json!({
+        "transaction": {
+            "signatures": [
+                "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW"
+            ],
+            "message": {
+                "header": {
+                    "numRequiredSignatures": 1,
+                    "numReadonlySignedAccounts": 0,
+                    "numReadonlyUnsignedAccounts": 3
+                },
+                "accountKeys": [
+                    "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU",
+                    "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM",
+                    "HN7cABqLq46Es1jh92dQQisAq662SmxELLLsHHe4YWrH",
+                    COMPUTE_BUDGET_PROGRAM,
+                    SWAP_PROGRAM,
+                    TOKEN_PROGRAM
+                ],
+                "recentBlockhash": "EkSnNWid2cvwEVnVx9aBqawnmiCNiDgp3gUdkDPTKN1N",
+                "instructions": [
+                    {"programIdIndex": 3, "accounts": [], "data": "3DdGGhkhJbjm", "stackHeight": null},
+                    {"programIdIndex": 3, "accounts": [], "data": "Fj2Eoy", "stackHeight": null},
+                    {"programIdIndex": 4, "accounts": [0, 1, 2, 5], "data": "2z6bTwD6AAr5", "stackHeight": null}
+                ]
+            }
+        },
+        "meta": {
+            "err": err,
+            "status": status,
+            "fee": fee,
+            "preBalances": [1_000_000_000u64, 2_039_280, 2_039_280, 1, 1_141_440, 934_087_680],
+            "postBalances": [1_000_000_000u64 - fee, 2_039_280, 2_039_280, 1, 1_141_440, 934_087_680],
+            "innerInstructions": [
+                {
+                    "index": 2,
+                    "instructions": [
+                        {"programIdIndex": 5, "accounts": [1, 2, 0], "data": "3Bxs4h24hBtQy9rw", "stackHeight": 2},
+                        {"programIdIndex": 5, "accounts": [2, 1, 0], "data": "3Bxs4Bc3VYuGVuZX", "stackHeight": 2}
+                    ]
+                }
+            ],
+            "logMessages": [
+                format!("Program {COMPUTE_BUDGET_PROGRAM} invoke [1]"),
+                format!("Program {COMPUTE_BUDGET_PROGRAM} success"),
+                format!("Program {COMPUTE_BUDGET_PROGRAM} invoke [1]"),
+                format!("Program {COMPUTE_BUDGET_PROGRAM} success"),
+                format!("Program {SWAP_PROGRAM} invoke [1]"),
+                "Program log: Instruction: Route".to_string(),
+                format!("Program {TOKEN_PROGRAM} invoke [2]"),
+                "Program log: Instruction: Transfer".to_string(),
+                format!("Program {TOKEN_PROGRAM} consumed 4645 of 180000 compute units"),
+                format!("Program {TOKEN_PROGRAM} success"),
+                format!("Program {SWAP_PROGRAM} consumed 60000 of 200000 compute units"),
+                format!("Program {SWAP_PROGRAM} success")
+            ],
+            "preTokenBalances": [],
+            "postTokenBalances": [],
+            "rewards": [],
+            "computeUnitsConsumed": 60_300 + (i as u64 % 100) * 100
+        },
+        "version": "legacy"
+    });
+    serde_json::from_value(value).expect("fixture交易格式错误")
+}
+
+fn fixture_blocks(block_count: usize, transactions_per_block: usize) -> Vec<CheapBlockTransaction> {
+    (0..block_count)
+        .map(|b| {
+            let slot = 300_000_000 + b as u64;
+            let block_time = Some(1_700_000_000 + b as i64);
+            let transactions = (0..transactions_per_block)
+                .map(|i| {
+                    ParsedVersionedTransactionWithStatusMeta::from_encoded_transaction_with_status_meta(
+                        fixture_transaction(b * transactions_per_block + i),
+                        slot,
+                        block_time,
+                    )
+                })
+                .collect::<Vec<_>>();
+            CheapBlockTransaction {
+                slot,
+                block_time,
+                block_height: Some(280_000_000 + b as u64),
+                transactions: Rc::new(transactions),
+            }
+        })
+        .collect()
+}
+
+fn fixture_expression() -> TransactionFilterExpression {
+    // (调用了swap程序 且 手续费在区间内) 或 (swap程序日志包含Route 且 非 只调用了token程序的顶层指令)
+    TransactionFilterExpression::Or(vec![
+        TransactionFilterExpression::And(vec![
+            TransactionFilterExpression::filter(ProgramIdFilter::new(
+                vec![SWAP_PROGRAM.to_string()],
+                ProgramInvocationScope::TopLevel,
+            )),
+            TransactionFilterExpression::filter(FeeRangeFilter::new(U64Range::new(
+                Some(20_000),
+                Some(40_000),
+            ))),
+        ]),
+        TransactionFilterExpression::And(vec![
+            TransactionFilterExpression::filter(LogMessageFilter::substring(
+                "Instruction: Route".to_string(),
+                Some(SWAP_PROGRAM.to_string()),
+            )),
+            !TransactionFilterExpression::filter(ProgramIdFilter::new(
+                vec![TOKEN_PROGRAM.to_string()],
+                ProgramInvocationScope::TopLevel,
+            )),
+        ]),
+    ])
+}
+
+fn bench_filter_blocks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("filter_blocks");
+    group.sample_size(10);
+    // 主网区块一般包含1000~1500笔交易
+    for block_count in [10usize, 100] {
+        let blocks = fixture_blocks(block_count, 1_200);
+        let empty = TransactionFilterExpression::default();
+        let expression = fixture_expression();
+
+        group.bench_with_input(
+            BenchmarkId::new("no_filter", block_count),
+            &blocks,
+            |b, blocks| {
+                b.iter(|| {
+                    let mut context = TransactionFilterContext::default();
+                    let mut statistics = TransactionFilterStatistics::default();
+                    black_box(empty.filter_blocks(blocks, &mut context, &mut statistics))
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("nested_expression", block_count),
+            &blocks,
+            |b, blocks| {
+                b.iter(|| {
+                    let mut context = TransactionFilterContext::default();
+                    let mut statistics = TransactionFilterStatistics::default();
+                    black_box(expression.filter_blocks(blocks, &mut context, &mut statistics))
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_filter_blocks);
+criterion_main!(benches);
@@ -1,2 +1,3 @@
 pub const API_BASE_URL: &'static str = "http://127.0.0.1/api";
 pub const WS_BASE_URL: &'static str = "ws://127.0.0.1/ws";
+pub const SOLANA_RPC_URL: &str = "https://api.mainnet-beta.solana.com";
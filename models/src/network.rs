@@ -1 +1,2 @@
 pub mod network_service;
+pub mod solana_rpc;
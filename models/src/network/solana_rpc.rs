@@ -0,0 +1,269 @@
+//! 直接访问Solana节点的JSON-RPC客户端，服务端接口尚未提供的按slot查询区块、地址历史在此请求节点
+
+use crate::constants::SOLANA_RPC_URL;
+use block_insight_cross::api::transaction::BlockTransaction;
+use reqwest::header::CONTENT_TYPE;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use solana_transaction_status_client_types::{
+    EncodedConfirmedTransactionWithStatusMeta, UiConfirmedBlock,
+};
+
+/// 单个批量请求中最多包含的调用数，公共节点对批量请求的大小有限制
+const BATCH_SIZE: usize = 20;
+
+/// 节点对被跳过的slot返回的错误码
+const SLOT_SKIPPED: i64 = -32007;
+/// 节点对已不在长期存储中、或被跳过的slot返回的错误码
+const LONG_TERM_STORAGE_SLOT_SKIPPED: i64 = -32009;
+
+#[derive(Debug, thiserror::Error)]
+pub enum RpcError {
+    #[error("请求节点失败: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("解析节点响应失败: {0}")]
+    Decode(#[from] serde_json::Error),
+    #[error("节点返回错误({code}): {message}")]
+    Node { code: i64, message: String },
+    #[error("节点未返回第{0}个调用的结果")]
+    MissingResponse(usize),
+}
+
+#[derive(Deserialize)]
+struct SignatureInfo {
+    signature: String,
+}
+
+#[derive(Deserialize)]
+struct RpcErrorObject {
+    code: i64,
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse<T> {
+    id: usize,
+    result: Option<T>,
+    error: Option<RpcErrorObject>,
+}
+
+impl<T> RpcResponse<T> {
+    fn into_result(self) -> Result<Option<T>, RpcError> {
+        match self.error {
+            Some(RpcErrorObject { code, message }) => Err(RpcError::Node { code, message }),
+            None => Ok(self.result),
+        }
+    }
+}
+
+/// 按slot范围获取的区块
+#[derive(Debug, Default)]
+pub struct FetchedBlocks {
+    /// 范围内的区块，按slot升序排列
+    pub blocks: Vec<BlockTransaction>,
+    /// 范围内被节点确认跳过的slot，按升序排列，尚未产生的slot不在其中
+    pub skipped: Vec<u64>,
+}
+
+/// 获取[start_slot, end_slot]范围内已确认的区块，超出节点当前slot的部分尚未产生，既不返回区块也不记为跳过
+pub async fn fetch_blocks(start_slot: u64, end_slot: u64) -> Result<FetchedBlocks, RpcError> {
+    let latest: u64 = call("getSlot", json!([{"commitment": "confirmed"}]))
+        .await?
+        .unwrap_or_default();
+    let end_slot = end_slot.min(latest);
+    if start_slot > end_slot {
+        return Ok(FetchedBlocks::default());
+    }
+    let slots: Vec<u64> = call(
+        "getBlocks",
+        json!([start_slot, end_slot, {"commitment": "confirmed"}]),
+    )
+    .await?
+    .unwrap_or_default();
+    let params = slots
+        .iter()
+        .map(|slot| {
+            json!([slot, {
+                "commitment": "confirmed",
+                "encoding": "json",
+                "maxSupportedTransactionVersion": 0,
+                "transactionDetails": "full",
+                "rewards": false,
+            }])
+        })
+        .collect::<Vec<_>>();
+    let mut fetched = FetchedBlocks {
+        blocks: Vec::with_capacity(slots.len()),
+        skipped: skipped_slots(start_slot, end_slot, &slots),
+    };
+    let results = call_batch::<UiConfirmedBlock>("getBlock", params).await?;
+    for (slot, result) in slots.into_iter().zip(results) {
+        match result {
+            Ok(Some(block)) => fetched.blocks.push(BlockTransaction {
+                slot,
+                block_time: block.block_time,
+                block_height: block.block_height,
+                transactions: block.transactions,
+            }),
+            Ok(None) => fetched.skipped.push(slot),
+            Err(RpcError::Node { code, .. })
+                if code == SLOT_SKIPPED || code == LONG_TERM_STORAGE_SLOT_SKIPPED =>
+            {
+                fetched.skipped.push(slot)
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    fetched.skipped.sort_unstable();
+    Ok(fetched)
+}
+
+/// 获取涉及指定地址的交易，按时间从新到旧排列。
+/// before、until为签名，分别只查询该签名之前(更旧)、之后(更新)的交易，节点已无法提供的交易会被忽略
+pub async fn fetch_transactions_for_address(
+    address: &str,
+    before: Option<&str>,
+    until: Option<&str>,
+    limit: u32,
+) -> Result<Vec<EncodedConfirmedTransactionWithStatusMeta>, RpcError> {
+    let signatures: Vec<SignatureInfo> = call(
+        "getSignaturesForAddress",
+        json!([address, {
+            "commitment": "confirmed",
+            "before": before,
+            "until": until,
+            "limit": limit,
+        }]),
+    )
+    .await?
+    .unwrap_or_default();
+    let params = signatures
+        .iter()
+        .map(|info| {
+            json!([info.signature, {
+                "commitment": "confirmed",
+                "encoding": "json",
+                "maxSupportedTransactionVersion": 0,
+            }])
+        })
+        .collect::<Vec<_>>();
+    let results =
+        call_batch::<EncodedConfirmedTransactionWithStatusMeta>("getTransaction", params).await?;
+    let mut transactions = Vec::with_capacity(results.len());
+    for result in results {
+        if let Some(transaction) = result? {
+            transactions.push(transaction);
+        }
+    }
+    Ok(transactions)
+}
+
+/// [start_slot, end_slot]范围内不在slots(升序)中的slot
+fn skipped_slots(start_slot: u64, end_slot: u64, slots: &[u64]) -> Vec<u64> {
+    (start_slot..=end_slot)
+        .filter(|slot| slots.binary_search(slot).is_err())
+        .collect()
+}
+
+async fn post(body: Value) -> Result<String, RpcError> {
+    let text = reqwest::Client::new()
+        .post(SOLANA_RPC_URL)
+        .header(CONTENT_TYPE, "application/json")
+        .body(body.to_string())
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    Ok(text)
+}
+
+fn request(id: usize, method: &str, params: Value) -> Value {
+    json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params})
+}
+
+async fn call<T: DeserializeOwned>(method: &str, params: Value) -> Result<Option<T>, RpcError> {
+    let text = post(request(0, method, params)).await?;
+    serde_json::from_str::<RpcResponse<T>>(&text)?.into_result()
+}
+
+/// 以批量请求调用同一方法，按参数顺序返回每个调用的结果
+async fn call_batch<T: DeserializeOwned>(
+    method: &str,
+    params: Vec<Value>,
+) -> Result<Vec<Result<Option<T>, RpcError>>, RpcError> {
+    let mut results = Vec::with_capacity(params.len());
+    for chunk in params.chunks(BATCH_SIZE) {
+        let body = chunk
+            .iter()
+            .enumerate()
+            .map(|(id, params)| request(id, method, params.clone()))
+            .collect();
+        let text = post(Value::Array(body)).await?;
+        results.extend(parse_batch(&text, chunk.len())?);
+    }
+    Ok(results)
+}
+
+/// 节点不保证批量响应的顺序，按请求的id重新排列
+fn parse_batch<T: DeserializeOwned>(
+    text: &str,
+    len: usize,
+) -> Result<Vec<Result<Option<T>, RpcError>>, RpcError> {
+    let mut responses = serde_json::from_str::<Vec<RpcResponse<T>>>(text)?;
+    responses.sort_by_key(|r| r.id);
+    if let Some(missing) = (0..len).find(|id| responses.get(*id).is_none_or(|r| r.id != *id)) {
+        return Err(RpcError::MissingResponse(missing));
+    }
+    Ok(responses
+        .into_iter()
+        .map(RpcResponse::into_result)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skipped_slots_are_the_gaps_in_the_range() {
+        assert_eq!(skipped_slots(10, 15, &[10, 12, 13]), vec![11, 14, 15]);
+        assert_eq!(skipped_slots(10, 12, &[10, 11, 12]), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn batch_responses_follow_request_order() {
+        let text = r#"[
+            {"jsonrpc": "2.0", "id": 2, "result": 30},
+            {"jsonrpc": "2.0", "id": 0, "result": 10},
+            {"jsonrpc": "2.0", "id": 1, "error": {"code": -32007, "message": "Slot 11 was skipped"}}
+        ]"#;
+        let results = parse_batch::<u64>(text, 3).unwrap();
+        assert!(matches!(results[0], Ok(Some(10))));
+        assert!(matches!(
+            results[1],
+            Err(RpcError::Node {
+                code: SLOT_SKIPPED,
+                ..
+            })
+        ));
+        assert!(matches!(results[2], Ok(Some(30))));
+    }
+
+    #[test]
+    fn null_result_is_none() {
+        let text = r#"[{"jsonrpc": "2.0", "id": 0, "result": null}]"#;
+        let results = parse_batch::<u64>(text, 1).unwrap();
+        assert!(matches!(results[0], Ok(None)));
+    }
+
+    #[test]
+    fn missing_response_is_an_error() {
+        let text = r#"[{"jsonrpc": "2.0", "id": 1, "result": 10}]"#;
+        assert!(matches!(
+            parse_batch::<u64>(text, 2),
+            Err(RpcError::MissingResponse(0))
+        ));
+    }
+}
@@ -1,3 +1,18 @@
 pub mod workspace_state;
 pub mod transaction_service;
-pub mod cheap_block_transaction;
\ No newline at end of file
+pub mod cheap_block_transaction;
+pub mod transaction_filter_expression;
+pub mod transaction_filter;
+pub mod transaction_sort;
+pub mod transaction_aggregation;
+pub mod transaction_encoding;
+pub mod address_history;
+pub mod transaction_cache;
+pub mod persistent_block_cache;
+pub mod transaction_import;
+pub mod transaction_export;
+pub mod transaction_filter_config;
+pub mod workspace_link;
+pub mod balance_change;
+pub mod transaction_comparison;
+pub mod log_tree;
\ No newline at end of file
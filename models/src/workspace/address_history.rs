@@ -0,0 +1,80 @@
+use anyhow::anyhow;
+use solana_pubkey::Pubkey;
+use solana_signature::Signature;
+use std::str::FromStr;
+
+/// 单页默认查询的签名数
+pub const DEFAULT_ADDRESS_HISTORY_LIMIT: u32 = 100;
+/// 单页最多查询的签名数
+pub const MAX_ADDRESS_HISTORY_LIMIT: u32 = 1000;
+
+/// 按帐号地址查询历史交易的参数，签名按时间从新到旧排列
+#[derive(Clone, Debug, PartialEq)]
+pub struct AddressHistoryQuery {
+    pub address: String,
+    /// 只查询该签名之前(更旧)的交易
+    pub before: Option<String>,
+    /// 只查询该签名之后(更新)的交易
+    pub until: Option<String>,
+    pub limit: u32,
+}
+
+impl AddressHistoryQuery {
+    pub fn new(address: String) -> Self {
+        AddressHistoryQuery {
+            address,
+            before: None,
+            until: None,
+            limit: DEFAULT_ADDRESS_HISTORY_LIMIT,
+        }
+    }
+
+    /// 检查地址、游标签名及数量是否合法
+    pub fn validate(&self) -> anyhow::Result<()> {
+        Pubkey::from_str(&self.address).map_err(|e| anyhow!("帐号地址格式错误: {e}"))?;
+        for cursor in [&self.before, &self.until].into_iter().flatten() {
+            Signature::from_str(cursor).map_err(|e| anyhow!("签名: {cursor} 格式错误: {e}"))?;
+        }
+        if self.limit == 0 || self.limit > MAX_ADDRESS_HISTORY_LIMIT {
+            return Err(anyhow!("数量必须在1到{MAX_ADDRESS_HISTORY_LIMIT}之间"));
+        }
+        Ok(())
+    }
+}
+
+/// 已加载的一页历史交易
+#[derive(Clone, Debug, PartialEq)]
+pub struct AddressHistoryPage {
+    pub query: AddressHistoryQuery,
+    /// 本页最新的签名
+    pub newest_signature: Option<String>,
+    /// 本页最旧的签名
+    pub oldest_signature: Option<String>,
+    /// 本页的交易数
+    pub count: usize,
+}
+
+impl AddressHistoryPage {
+    /// 是否可能还有更旧的交易
+    pub fn has_older(&self) -> bool {
+        self.count as u32 >= self.query.limit && self.oldest_signature.is_some()
+    }
+
+    /// 更旧的一页，游标为本页最旧的签名
+    pub fn older(&self) -> Option<AddressHistoryQuery> {
+        self.has_older().then(|| AddressHistoryQuery {
+            before: self.oldest_signature.clone(),
+            until: self.query.until.clone(),
+            ..self.query.clone()
+        })
+    }
+
+    /// 更新的一页，游标为本页最新的签名
+    pub fn newer(&self) -> Option<AddressHistoryQuery> {
+        self.newest_signature.as_ref().map(|newest| AddressHistoryQuery {
+            before: None,
+            until: Some(newest.clone()),
+            ..self.query.clone()
+        })
+    }
+}
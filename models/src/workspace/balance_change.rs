@@ -0,0 +1,173 @@
+use crate::workspace::cheap_block_transaction::ParsedVersionedTransactionWithStatusMeta;
+use crate::workspace::transaction_encoding::AccountKey;
+use solana_transaction_status_client_types::{UiTransactionStatusMeta, UiTransactionTokenBalance};
+use std::collections::BTreeMap;
+
+/// SOL的精度，1 SOL = 10^9 lamports
+pub const SOL_DECIMALS: u8 = 9;
+
+/// 单个帐号在交易前后的SOL余额(lamports)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SolBalanceChange {
+    pub account: AccountKey,
+    pub pre: u64,
+    pub post: u64,
+}
+
+impl SolBalanceChange {
+    pub fn delta(&self) -> i128 {
+        self.post as i128 - self.pre as i128
+    }
+}
+
+/// 交易中每个帐号的SOL余额变化，顺序与帐号列表一致，没有元数据时为空
+pub fn sol_balance_changes(
+    transaction: &ParsedVersionedTransactionWithStatusMeta,
+) -> Vec<SolBalanceChange> {
+    let Some(meta) = transaction.meta() else {
+        return Vec::new();
+    };
+    transaction
+        .account_keys()
+        .into_iter()
+        .zip(meta.pre_balances.iter().zip(meta.post_balances.iter()))
+        .map(|(account, (&pre, &post))| SolBalanceChange { account, pre, post })
+        .collect()
+}
+
+/// 余额增加最多及减少最多的帐号下标，各最多n个，余额不变的帐号不计入
+pub fn largest_sol_changes(changes: &[SolBalanceChange], n: usize) -> (Vec<usize>, Vec<usize>) {
+    let mut indices = (0..changes.len())
+        .filter(|&i| changes[i].delta() != 0)
+        .collect::<Vec<_>>();
+    indices.sort_by_key(|&i| std::cmp::Reverse(changes[i].delta()));
+    let gainers = indices
+        .iter()
+        .copied()
+        .take_while(|&i| changes[i].delta() > 0)
+        .take(n)
+        .collect();
+    let losers = indices
+        .iter()
+        .rev()
+        .copied()
+        .take_while(|&i| changes[i].delta() < 0)
+        .take(n)
+        .collect();
+    (gainers, losers)
+}
+
+/// 同一所有者持有的同一种代币在交易前后的余额，数量为最小单位
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TokenBalanceChange {
+    /// 代币帐号的所有者，旧交易的元数据中可能缺失
+    pub owner: Option<String>,
+    pub mint: String,
+    pub decimals: u8,
+    pub pre: u128,
+    pub post: u128,
+}
+
+impl TokenBalanceChange {
+    pub fn delta(&self) -> i128 {
+        self.post as i128 - self.pre as i128
+    }
+
+    /// 按精度换算后的余额变化
+    pub fn ui_delta(&self) -> f64 {
+        self.delta() as f64 / 10f64.powi(self.decimals as i32)
+    }
+}
+
+/// 根据交易元数据中的pre/post token balances计算代币余额变化
+pub fn compute_token_balance_changes(meta: &UiTransactionStatusMeta) -> Vec<TokenBalanceChange> {
+    let pre = Option::<&Vec<UiTransactionTokenBalance>>::from(meta.pre_token_balances.as_ref());
+    let post = Option::<&Vec<UiTransactionTokenBalance>>::from(meta.post_token_balances.as_ref());
+    token_balance_changes(
+        pre.map(Vec::as_slice).unwrap_or_default(),
+        post.map(Vec::as_slice).unwrap_or_default(),
+    )
+}
+
+/// 按所有者及mint合并交易前后的代币余额，同一所有者的多个代币帐号会被合并，结果按(所有者, mint)排序
+pub fn token_balance_changes(
+    pre: &[UiTransactionTokenBalance],
+    post: &[UiTransactionTokenBalance],
+) -> Vec<TokenBalanceChange> {
+    let mut changes: BTreeMap<(Option<String>, String), TokenBalanceChange> = BTreeMap::new();
+    let mut add = |balance: &UiTransactionTokenBalance, is_post: bool| {
+        let owner = Option::<String>::from(balance.owner.clone());
+        let amount = balance
+            .ui_token_amount
+            .amount
+            .parse::<u128>()
+            .unwrap_or_default();
+        let change = changes
+            .entry((owner.clone(), balance.mint.clone()))
+            .or_insert_with(|| TokenBalanceChange {
+                owner,
+                mint: balance.mint.clone(),
+                decimals: balance.ui_token_amount.decimals,
+                pre: 0,
+                post: 0,
+            });
+        if is_post {
+            change.post += amount;
+        } else {
+            change.pre += amount;
+        }
+    };
+    for balance in pre {
+        add(balance, false);
+    }
+    for balance in post {
+        add(balance, true);
+    }
+    changes.into_values().collect()
+}
+
+/// 将最小单位的数量按精度格式化，去掉小数部分末尾的0，
+/// 精度超出u128的表示范围时直接输出最小单位的数量
+pub fn format_token_amount(amount: i128, decimals: u8) -> String {
+    let Some(base) = 10u128.checked_pow(decimals as u32) else {
+        return amount.to_string();
+    };
+    let sign = if amount < 0 { "-" } else { "" };
+    let amount = amount.unsigned_abs();
+    let integer = amount / base;
+    let fraction = amount % base;
+    if fraction == 0 {
+        return format!("{sign}{integer}");
+    }
+    let fraction = format!("{fraction:0width$}", width = decimals as usize);
+    format!("{sign}{integer}.{}", fraction.trim_end_matches('0'))
+}
+
+/// 将lamports格式化为SOL
+pub fn format_lamports(lamports: i128) -> String {
+    format_token_amount(lamports, SOL_DECIMALS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_token_amounts() {
+        assert_eq!(format_token_amount(1_500_000, 6), "1.5");
+        assert_eq!(format_token_amount(-1_000_000, 6), "-1");
+        assert_eq!(format_token_amount(-5, 3), "-0.005");
+        assert_eq!(format_token_amount(42, 0), "42");
+        assert_eq!(format_lamports(1_000_000_001), "1.000000001");
+    }
+
+    #[test]
+    fn large_decimals_do_not_overflow() {
+        assert_eq!(
+            format_token_amount(123, 38),
+            "0.00000000000000000000000000000000000123"
+        );
+        assert_eq!(format_token_amount(123, 39), "123");
+        assert_eq!(format_token_amount(-123, u8::MAX), "-123");
+    }
+}
@@ -1,14 +1,26 @@
-use crate::workspace::transaction_service::ParsedEncodedConfirmedTransactionWithStatusMeta;
+use crate::workspace::balance_change::{TokenBalanceChange, compute_token_balance_changes};
+use crate::workspace::transaction_encoding::{
+    AccountKey, JsonParsedInstructions, message_account_keys, normalize_encoded_transaction,
+    ui_transaction_to_versioned, versioned_transaction_to_parsed_ui, versioned_transaction_to_ui,
+};
+use block_insight_cross::api::transaction::BlockTransaction;
+use block_insight_cross::parsed_instruction::{ParsedInstruction, ParsedInstructionList};
+use block_insight_cross::transaction::transaction_filter::{
+    TransactionMeta, TransactionPropsProvider,
+};
+use block_insight_cross::utils::TransactionAccounts;
 use dioxus::logger::tracing::error;
 use serde::{Deserialize, Serialize};
+use solana_pubkey::Pubkey;
+use solana_signature::Signature;
 use solana_transaction::versioned::{TransactionVersion, VersionedTransaction};
 use solana_transaction_status_client_types::{
     EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction,
-    EncodedTransactionWithStatusMeta, UiTransactionStatusMeta,
+    EncodedTransactionWithStatusMeta, UiLoadedAddresses, UiTransactionStatusMeta,
+    UiTransactionTokenBalance,
 };
+use std::collections::BTreeMap;
 use std::rc::Rc;
-use block_insight_cross::api::transaction::BlockTransaction;
-use block_insight_cross::parsed_instruction::ParsedInstructionList;
 
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct VersionedTransactionWithStatusMeta {
@@ -20,10 +32,19 @@ pub struct VersionedTransactionWithStatusMeta {
     pub block_time: Option<i64>,
 }
 
+/// 解码后的交易，工作空间中所有交易都以此形式保存
 #[derive(Debug, PartialEq, Clone)]
 pub struct ParsedVersionedTransactionWithStatusMeta {
     pub transaction: Rc<VersionedTransactionWithStatusMeta>,
     pub parsed_instructions: Rc<ParsedInstructionList>,
+    /// jsonParsed编码中已被服务端解析的指令
+    pub json_parsed_instructions: Rc<JsonParsedInstructions>,
+    /// 签名的字符串形式，供过滤器使用
+    signatures: Rc<Vec<String>>,
+    /// 静态帐号的字符串形式，供过滤器使用
+    account_keys: Rc<Vec<String>>,
+    /// 按所有者及mint合并的代币余额变化
+    token_balance_changes: Rc<Vec<TokenBalanceChange>>,
 }
 
 impl ParsedVersionedTransactionWithStatusMeta {
@@ -33,21 +54,257 @@ impl ParsedVersionedTransactionWithStatusMeta {
             transaction,
             block_time,
         } = transaction;
-        let EncodedTransactionWithStatusMeta {
-            transaction,
-            meta,
-            version,
-        } = transaction;
-        let transaction = transaction.decode();
+        Self::from_encoded_transaction_with_status_meta(transaction, slot, block_time)
+    }
+
+    /// 无论服务端返回何种编码，交易都会先被统一为Raw格式的Json交易(见[normalize_encoded_transaction])，
+    /// 再解码为[VersionedTransaction]，指令基于解码后交易的Message解析
+    pub fn from_encoded_transaction_with_status_meta(
+        transaction: EncodedTransactionWithStatusMeta,
+        slot: u64,
+        block_time: Option<i64>,
+    ) -> Self {
+        let (transaction, json_parsed_instructions) = normalize_encoded_transaction(transaction);
+        let decoded = match &transaction.transaction {
+            EncodedTransaction::Json(tx) => match ui_transaction_to_versioned(tx) {
+                Ok(tx) => Some(tx),
+                Err(e) => {
+                    error!("解码交易失败: {e}");
+                    None
+                }
+            },
+            _ => {
+                error!("交易数据未能转换为Json格式");
+                None
+            }
+        };
+        // 未能解码的交易只剩编码后的形式可用
+        let parsed_instructions = match &decoded {
+            Some(_) => None,
+            None => Some(ParsedInstructionList::from(&transaction)),
+        };
+        let EncodedTransactionWithStatusMeta { meta, version, .. } = transaction;
         let versioned = VersionedTransactionWithStatusMeta {
             slot,
-            transaction,
+            transaction: decoded,
             meta,
             version,
             block_time,
         };
+        Self::from_parts(versioned, parsed_instructions, json_parsed_instructions)
+    }
+
+    /// 从已解码的交易重新构建，交易为空时返回None。
+    /// 解码后的交易中已没有jsonParsed指令的解析结果，需由调用者一并提供
+    pub fn from_versioned(
+        transaction: VersionedTransactionWithStatusMeta,
+        json_parsed_instructions: JsonParsedInstructions,
+    ) -> Option<Self> {
+        transaction.transaction.as_ref()?;
+        Some(Self::from_parts(
+            transaction,
+            None,
+            json_parsed_instructions,
+        ))
+    }
+
+    /// 未提供指令列表时基于解码后交易的Message解析
+    fn from_parts(
+        mut transaction: VersionedTransactionWithStatusMeta,
+        parsed_instructions: Option<ParsedInstructionList>,
+        json_parsed_instructions: JsonParsedInstructions,
+    ) -> Self {
+        let parsed_instructions = match (parsed_instructions, &transaction.transaction) {
+            (Some(parsed_instructions), _) => parsed_instructions,
+            (None, Some(tx)) => parse_instructions(tx, &mut transaction.meta),
+            (None, None) => ParsedInstructionList::default(),
+        };
+        let (signatures, account_keys) = transaction
+            .transaction
+            .as_ref()
+            .map(|tx| {
+                let signatures: Vec<String> =
+                    tx.signatures.iter().map(|s| s.to_string()).collect();
+                let account_keys: Vec<String> = tx
+                    .message
+                    .static_account_keys()
+                    .iter()
+                    .map(|k| k.to_string())
+                    .collect();
+                (signatures, account_keys)
+            })
+            .unwrap_or_default();
+        let token_balance_changes = transaction
+            .meta
+            .as_ref()
+            .map(compute_token_balance_changes)
+            .unwrap_or_default();
+        ParsedVersionedTransactionWithStatusMeta {
+            transaction: Rc::new(transaction),
+            parsed_instructions: Rc::new(parsed_instructions),
+            json_parsed_instructions: Rc::new(json_parsed_instructions),
+            signatures: Rc::new(signatures),
+            account_keys: Rc::new(account_keys),
+            token_balance_changes: Rc::new(token_balance_changes),
+        }
+    }
+
+    /// 重新编码为Json格式的交易，交易未能解码时返回None。
+    /// 有jsonParsed指令时以jsonParsed格式编码，以保留其解析结果，否则为Raw格式
+    pub fn to_encoded(&self) -> Option<EncodedConfirmedTransactionWithStatusMeta> {
+        let transaction = self.transaction.transaction.as_ref()?;
+        let mut meta = self.transaction.meta.clone();
+        let ui_transaction = if self.json_parsed_instructions.is_empty() {
+            versioned_transaction_to_ui(transaction)
+        } else {
+            versioned_transaction_to_parsed_ui(
+                transaction,
+                meta.as_mut(),
+                &self.json_parsed_instructions,
+            )
+        };
+        Some(EncodedConfirmedTransactionWithStatusMeta {
+            slot: self.slot(),
+            transaction: EncodedTransactionWithStatusMeta {
+                transaction: EncodedTransaction::Json(ui_transaction),
+                meta,
+                version: self.transaction.version.clone(),
+            },
+            block_time: self.block_time(),
+        })
+    }
 
-        todo!("new parsed versioned");
+    pub fn slot(&self) -> u64 {
+        self.transaction.slot
+    }
+
+    pub fn block_time(&self) -> Option<i64> {
+        self.transaction.block_time
+    }
+
+    pub fn meta(&self) -> Option<&UiTransactionStatusMeta> {
+        self.transaction.meta.as_ref()
+    }
+
+    /// 交易的所有签名，解码失败时为空
+    pub fn signatures(&self) -> &[Signature] {
+        self.transaction
+            .transaction
+            .as_ref()
+            .map(|tx| tx.signatures.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// 交易的第一个签名，即交易ID
+    pub fn signature(&self) -> Option<&Signature> {
+        self.signatures().first()
+    }
+
+    /// 手续费支付者，即第一个帐号
+    pub fn fee_payer(&self) -> Option<&Pubkey> {
+        self.transaction
+            .transaction
+            .as_ref()
+            .and_then(|tx| tx.message.static_account_keys().first())
+    }
+
+    /// 交易中的所有帐号(包括通过地址表加载的)及其签名/可写权限，顺序与指令中的帐号下标一致
+    pub fn account_keys(&self) -> Vec<AccountKey> {
+        match &self.transaction.transaction {
+            Some(tx) => message_account_keys(&tx.message, self.loaded_addresses()),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn fee(&self) -> Option<u64> {
+        self.meta().map(|m| m.fee)
+    }
+
+    pub fn compute_units_consumed(&self) -> Option<u64> {
+        self.meta()
+            .and_then(|m| Option::<u64>::from(m.compute_units_consumed.clone()))
+    }
+
+    /// 交易是否执行成功，没有元数据时返回None
+    pub fn is_success(&self) -> Option<bool> {
+        self.meta().map(|m| m.err.is_none())
+    }
+
+    /// 按所有者及mint合并的代币余额变化，按(所有者, mint)排序
+    pub fn token_balance_changes(&self) -> &[TokenBalanceChange] {
+        self.token_balance_changes.as_slice()
+    }
+
+    fn loaded_addresses(&self) -> Option<&UiLoadedAddresses> {
+        self.meta()
+            .and_then(|m| Option::<&UiLoadedAddresses>::from(m.loaded_addresses.as_ref()))
+    }
+}
+
+/// [ParsedInstructionList]只能从编码后的交易构建，这里直接以解码后交易的Message生成Raw格式的Json交易，
+/// 不经过统一编码及重新解码。元数据中的内部指令同样参与解析，只是临时移入，不会被复制
+fn parse_instructions(
+    transaction: &VersionedTransaction,
+    meta: &mut Option<UiTransactionStatusMeta>,
+) -> ParsedInstructionList {
+    let encoded = EncodedTransactionWithStatusMeta {
+        transaction: EncodedTransaction::Json(versioned_transaction_to_ui(transaction)),
+        meta: meta.take(),
+        version: None,
+    };
+    let parsed_instructions = ParsedInstructionList::from(&encoded);
+    *meta = encoded.meta;
+    parsed_instructions
+}
+
+impl TransactionPropsProvider for ParsedVersionedTransactionWithStatusMeta {
+    fn get_accounts(&self) -> TransactionAccounts<'_, String> {
+        let loaded = self.loaded_addresses();
+        let account_keys = self
+            .transaction
+            .transaction
+            .is_some()
+            .then(|| self.account_keys.as_slice());
+        TransactionAccounts::from_accounts(
+            account_keys,
+            loaded.map(|l| l.writable.as_slice()),
+            loaded.map(|l| l.readonly.as_slice()),
+        )
+    }
+
+    fn get_signatures(&self) -> Option<&[String]> {
+        self.transaction
+            .transaction
+            .is_some()
+            .then(|| self.signatures.as_slice())
+    }
+
+    fn get_parsed_instructions(&self) -> Option<&[ParsedInstruction]> {
+        Some(self.parsed_instructions.as_slice())
+    }
+
+    fn get_meta(&self) -> Option<TransactionMeta<'_>> {
+        self.meta().map(|m| TransactionMeta {
+            err: m.err.as_ref(),
+            status: &m.status,
+            fee: m.fee,
+            pre_balances: m.pre_balances.as_slice(),
+            post_balances: m.post_balances.as_slice(),
+            log_messages: m.log_messages.as_ref().map(|m| m.as_slice()),
+            compute_units_consumed: m.compute_units_consumed.as_ref().map(|c| *c),
+        })
+    }
+
+    fn get_pre_token_balances(&self) -> Option<&[UiTransactionTokenBalance]> {
+        self.meta()
+            .and_then(|m| Option::<&Vec<_>>::from(m.pre_token_balances.as_ref()))
+            .map(Vec::as_slice)
+    }
+
+    fn get_post_token_balances(&self) -> Option<&[UiTransactionTokenBalance]> {
+        self.meta()
+            .and_then(|m| Option::<&Vec<_>>::from(m.post_token_balances.as_ref()))
+            .map(Vec::as_slice)
     }
 }
 
@@ -88,57 +345,186 @@ pub struct CheapBlockTransaction {
     pub slot: u64,
     pub block_time: Option<i64>,
     pub block_height: Option<u64>,
-    pub transactions: Rc<Vec<ParsedEncodedConfirmedTransactionWithStatusMeta>>,
+    pub transactions: Rc<Vec<ParsedVersionedTransactionWithStatusMeta>>,
 }
 
 impl CheapBlockTransaction {
-    /// BlockTransaction转化为CheapBlockTransaction,并检查指定的签名是否在该区别中，如果在，则返回
-    pub fn from_block_transaction(
-        block_transaction: BlockTransaction,
-        signature: &String,
-    ) -> (
-        Self,
-        Option<ParsedEncodedConfirmedTransactionWithStatusMeta>,
-    ) {
+    /// BlockTransaction转化为CheapBlockTransaction
+    pub fn from_block(block_transaction: BlockTransaction) -> Self {
         let BlockTransaction {
             slot,
             block_time,
             block_height,
             transactions,
         } = block_transaction;
-        let mut looking_tx = Option::None;
-        let transactions = if let Some(transactions) = transactions {
-            let tx = transactions
-                .into_iter()
-                .map(|tx| {
-                    let v =
-                        ParsedEncodedConfirmedTransactionWithStatusMeta::from_encoded_transaction_with_status_meta(tx, slot, block_time);
-                    match &v.transaction.transaction.transaction {
-                        EncodedTransaction::Json(tx) => {
-                            if tx.signatures.contains(signature) {
-                                looking_tx.replace(v.clone());
-                            }
-                        }
-                        _ => {
-                            error!("意外的文件格式，目前只支持Json编码的交易数据")
-                        }
-                    }
-                    v
-                })
-                .collect();
-            Rc::new(tx)
-
-        } else {
-            Rc::new(vec![])
-        };
-        let cheap = CheapBlockTransaction {
+        let transactions = transactions
+            .unwrap_or_default()
+            .into_iter()
+            .map(|tx| {
+                ParsedVersionedTransactionWithStatusMeta::from_encoded_transaction_with_status_meta(
+                    tx, slot, block_time,
+                )
+            })
+            .collect();
+        CheapBlockTransaction {
             slot,
             block_time,
             block_height,
-            transactions,
-        };
-        (cheap, looking_tx)
+            transactions: Rc::new(transactions),
+        }
+    }
+
+    /// 将零散的交易按slot归并为区块，区块按slot升序排列，块内交易保持原顺序，
+    /// 由于没有完整的区块数据，区块高度为空
+    pub fn group_by_slot(
+        transactions: impl IntoIterator<Item = ParsedVersionedTransactionWithStatusMeta>,
+    ) -> Vec<Self> {
+        let mut blocks: BTreeMap<u64, (Option<i64>, Vec<_>)> = BTreeMap::new();
+        for tx in transactions {
+            let (_, txs) = blocks
+                .entry(tx.slot())
+                .or_insert_with(|| (tx.block_time(), Vec::new()));
+            txs.push(tx);
+        }
+        blocks
+            .into_iter()
+            .map(|(slot, (block_time, transactions))| CheapBlockTransaction {
+                slot,
+                block_time,
+                block_height: None,
+                transactions: Rc::new(transactions),
+            })
+            .collect()
     }
 }
 
 // impl TransactionPropsProvider for CheapBlockTransaction {}
+
+/// 过滤后的区块，只记录通过过滤的交易在原区块中的下标，不复制交易数据
+#[derive(Debug, PartialEq, Clone)]
+pub struct FilteredBlockTransaction {
+    /// 未过滤的原区块
+    pub block: CheapBlockTransaction,
+    /// 通过过滤的交易在原区块中的下标，按当前的排序方式排列
+    pub indices: Rc<Vec<usize>>,
+}
+
+impl FilteredBlockTransaction {
+    /// 不做任何过滤，保留区块中的所有交易
+    pub fn unfiltered(block: &CheapBlockTransaction) -> Self {
+        FilteredBlockTransaction {
+            block: block.clone(),
+            indices: Rc::new((0..block.transactions.len()).collect()),
+        }
+    }
+
+    pub fn slot(&self) -> u64 {
+        self.block.slot
+    }
+
+    /// 通过过滤的交易数
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// 依次返回通过过滤的交易及其在原区块中的下标
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = (usize, &ParsedVersionedTransactionWithStatusMeta)> {
+        self.indices
+            .iter()
+            .map(|&i| (i, &self.block.transactions[i]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn encoded_transaction() -> EncodedTransactionWithStatusMeta {
+        serde_json::from_value(json!({
+            "transaction": {
+                "signatures": [
+                    "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW"
+                ],
+                "message": {
+                    "header": {
+                        "numRequiredSignatures": 1,
+                        "numReadonlySignedAccounts": 0,
+                        "numReadonlyUnsignedAccounts": 2
+                    },
+                    "accountKeys": [
+                        "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU",
+                        "ComputeBudget111111111111111111111111111111",
+                        "11111111111111111111111111111111"
+                    ],
+                    "recentBlockhash": "EkSnNWid2cvwEVnVx9aBqawnmiCNiDgp3gUdkDPTKN1N",
+                    "instructions": [
+                        {"programIdIndex": 1, "accounts": [], "data": "3DdGGhkhJbjm", "stackHeight": null}
+                    ]
+                }
+            },
+            "meta": {
+                "err": null,
+                "status": {"Ok": null},
+                "fee": 5000,
+                "preBalances": [1_000_000_000u64, 1, 1],
+                "postBalances": [999_995_000u64, 1, 1],
+                "innerInstructions": [
+                    {
+                        "index": 0,
+                        "instructions": [
+                            {"programIdIndex": 2, "accounts": [0], "data": "3Bxs4h24hBtQy9rw", "stackHeight": 2}
+                        ]
+                    }
+                ],
+                "logMessages": [],
+                "preTokenBalances": [],
+                "postTokenBalances": [],
+                "rewards": []
+            },
+            "version": "legacy"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn from_versioned_matches_from_encoded() {
+        let parsed =
+            ParsedVersionedTransactionWithStatusMeta::from_encoded_transaction_with_status_meta(
+                encoded_transaction(),
+                100,
+                Some(1_700_000_000),
+            );
+        assert!(parsed.transaction.transaction.is_some());
+        let rebuilt = ParsedVersionedTransactionWithStatusMeta::from_versioned(
+            parsed.transaction.as_ref().clone(),
+            parsed.json_parsed_instructions.as_ref().clone(),
+        )
+        .unwrap();
+        assert_eq!(rebuilt, parsed);
+    }
+
+    #[test]
+    fn from_versioned_requires_a_decoded_transaction() {
+        let transaction = VersionedTransactionWithStatusMeta {
+            slot: 100,
+            transaction: None,
+            meta: None,
+            version: None,
+            block_time: None,
+        };
+        assert!(
+            ParsedVersionedTransactionWithStatusMeta::from_versioned(
+                transaction,
+                JsonParsedInstructions::default()
+            )
+            .is_none()
+        );
+    }
+}
@@ -0,0 +1,263 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use solana_pubkey::Pubkey;
+use std::str::FromStr;
+
+/// 程序调用的结果
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InvocationResult {
+    Success,
+    /// 失败原因
+    Failed(String),
+}
+
+/// 一次程序调用期间输出的日志，从"Program <id> invoke [n]"开始，到success/failed结束
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvocationFrame {
+    pub program_id: String,
+    /// 调用深度，顶层指令为1
+    pub depth: usize,
+    pub entries: Vec<LogEntry>,
+    /// (消耗的CU, CU上限)
+    pub compute_units: Option<(u64, u64)>,
+    /// 日志被截断时为None
+    pub result: Option<InvocationResult>,
+}
+
+/// 日志树中的一项
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LogEntry {
+    /// 嵌套的程序调用
+    Invocation(InvocationFrame),
+    /// "Program log: "之后的内容
+    Log(String),
+    /// "Program data: "之后以空格分隔的base64数据，及其解码后的十六进制，解码失败时为None
+    Data(Vec<(String, Option<String>)>),
+    /// 其它无法识别的日志，原样保留
+    Other(String),
+}
+
+/// 程序日志及数据的前缀，程序输出的内容可以是任意文本，需先于调用/结束日志识别
+const PROGRAM_LOG_PREFIX: &str = "Program log: ";
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+/// 取出"Program <id> "之后的内容，id必须是合法的公钥
+fn strip_program_id(log: &str) -> Option<(&str, &str)> {
+    let rest = log.strip_prefix("Program ")?;
+    let (id, rest) = rest.split_once(' ')?;
+    Pubkey::from_str(id).ok()?;
+    Some((id, rest))
+}
+
+/// 是否为程序通过msg!或sol_log_data输出的日志
+pub(crate) fn is_program_output(log: &str) -> bool {
+    log.starts_with(PROGRAM_LOG_PREFIX) || log.starts_with(PROGRAM_DATA_PREFIX)
+}
+
+/// 解析 "Program <id> invoke [n]"，返回程序ID及调用深度
+pub(crate) fn parse_invoke(log: &str) -> Option<(&str, usize)> {
+    let (id, rest) = strip_program_id(log)?;
+    let depth = rest.strip_prefix("invoke [")?.strip_suffix(']')?;
+    Some((id, depth.parse().ok()?))
+}
+
+/// 解析 "Program <id> success" 及 "Program <id> failed: ..."
+pub(crate) fn parse_invoke_end(log: &str) -> Option<(&str, InvocationResult)> {
+    let (id, rest) = strip_program_id(log)?;
+    if rest == "success" {
+        return Some((id, InvocationResult::Success));
+    }
+    let reason = rest.strip_prefix("failed")?;
+    let reason = reason.strip_prefix(':').unwrap_or(reason).trim();
+    Some((id, InvocationResult::Failed(reason.to_string())))
+}
+
+/// 解析 "Program <id> consumed <n> of <limit> compute units"
+fn parse_consumed(log: &str) -> Option<(&str, u64, u64)> {
+    let (id, rest) = strip_program_id(log)?;
+    let rest = rest.strip_prefix("consumed ")?;
+    let (consumed, rest) = rest.split_once(" of ")?;
+    let limit = rest.strip_suffix(" compute units")?;
+    Some((id, consumed.parse().ok()?, limit.parse().ok()?))
+}
+
+fn decode_program_data(data: &str) -> Vec<(String, Option<String>)> {
+    data.split_whitespace()
+        .map(|chunk| {
+            let hex = STANDARD
+                .decode(chunk)
+                .ok()
+                .map(|bytes| bytes.iter().map(|b| format!("{b:02x}")).collect::<String>());
+            (chunk.to_string(), hex)
+        })
+        .collect()
+}
+
+/// 将交易日志解析为程序调用树，返回顶层的日志项。
+/// 日志被截断时，未结束的调用会保留已解析的内容，其结果为None
+pub fn parse_log_tree(logs: &[String]) -> Vec<LogEntry> {
+    let mut root = Vec::new();
+    let mut stack: Vec<InvocationFrame> = Vec::new();
+
+    fn push(root: &mut Vec<LogEntry>, stack: &mut [InvocationFrame], entry: LogEntry) {
+        match stack.last_mut() {
+            Some(frame) => frame.entries.push(entry),
+            None => root.push(entry),
+        }
+    }
+
+    for log in logs {
+        if let Some(message) = log.strip_prefix(PROGRAM_LOG_PREFIX) {
+            push(&mut root, &mut stack, LogEntry::Log(message.to_string()));
+        } else if let Some(data) = log.strip_prefix(PROGRAM_DATA_PREFIX) {
+            push(
+                &mut root,
+                &mut stack,
+                LogEntry::Data(decode_program_data(data)),
+            );
+        } else if let Some((program_id, depth)) = parse_invoke(log) {
+            stack.push(InvocationFrame {
+                program_id: program_id.to_string(),
+                depth,
+                entries: Vec::new(),
+                compute_units: None,
+                result: None,
+            });
+        } else if let Some((program_id, consumed, limit)) = parse_consumed(log) {
+            match stack.last_mut() {
+                Some(frame) if frame.program_id == program_id => {
+                    frame.compute_units = Some((consumed, limit))
+                }
+                _ => push(&mut root, &mut stack, LogEntry::Other(log.clone())),
+            }
+        } else if let Some((program_id, result)) = parse_invoke_end(log) {
+            match stack.pop() {
+                Some(mut frame) if frame.program_id == program_id => {
+                    frame.result = Some(result);
+                    push(&mut root, &mut stack, LogEntry::Invocation(frame));
+                }
+                Some(frame) => {
+                    stack.push(frame);
+                    push(&mut root, &mut stack, LogEntry::Other(log.clone()));
+                }
+                None => root.push(LogEntry::Other(log.clone())),
+            }
+        } else {
+            push(&mut root, &mut stack, LogEntry::Other(log.clone()));
+        }
+    }
+
+    // 日志被截断，未结束的调用依次并入上层
+    while let Some(frame) = stack.pop() {
+        push(&mut root, &mut stack, LogEntry::Invocation(frame));
+    }
+    root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTER: &str = "ComputeBudget111111111111111111111111111111";
+    const INNER: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+    fn logs(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|l| l.to_string()).collect()
+    }
+
+    fn frame(entry: &LogEntry) -> &InvocationFrame {
+        match entry {
+            LogEntry::Invocation(frame) => frame,
+            other => panic!("不是程序调用: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn nests_cpi_under_caller() {
+        let tree = parse_log_tree(&logs(&[
+            &format!("Program {OUTER} invoke [1]"),
+            "Program log: outer",
+            &format!("Program {INNER} invoke [2]"),
+            "Program data: AQI= !!!",
+            &format!("Program {INNER} consumed 100 of 200 compute units"),
+            &format!("Program {INNER} success"),
+            &format!("Program {OUTER} consumed 300 of 400 compute units"),
+            &format!("Program {OUTER} failed: custom program error: 0x1"),
+        ]));
+        assert_eq!(tree.len(), 1);
+        let outer = frame(&tree[0]);
+        assert_eq!(outer.program_id, OUTER);
+        assert_eq!(outer.depth, 1);
+        assert_eq!(outer.compute_units, Some((300, 400)));
+        assert_eq!(
+            outer.result,
+            Some(InvocationResult::Failed(
+                "custom program error: 0x1".to_string()
+            ))
+        );
+        assert_eq!(outer.entries[0], LogEntry::Log("outer".to_string()));
+        let inner = frame(&outer.entries[1]);
+        assert_eq!(inner.program_id, INNER);
+        assert_eq!(inner.depth, 2);
+        assert_eq!(inner.compute_units, Some((100, 200)));
+        assert_eq!(inner.result, Some(InvocationResult::Success));
+        assert_eq!(
+            inner.entries,
+            vec![LogEntry::Data(vec![
+                ("AQI=".to_string(), Some("0102".to_string())),
+                ("!!!".to_string(), None),
+            ])]
+        );
+    }
+
+    #[test]
+    fn program_output_is_not_parsed_as_invocation() {
+        let tree = parse_log_tree(&logs(&[
+            &format!("Program {OUTER} invoke [1]"),
+            "Program log: success",
+            "Program log: failed to transfer",
+            &format!("Program log: {INNER} invoke [2]"),
+            &format!("Program {OUTER} success"),
+        ]));
+        assert_eq!(tree.len(), 1);
+        let outer = frame(&tree[0]);
+        assert_eq!(outer.result, Some(InvocationResult::Success));
+        assert_eq!(
+            outer.entries,
+            vec![
+                LogEntry::Log("success".to_string()),
+                LogEntry::Log("failed to transfer".to_string()),
+                LogEntry::Log(format!("{INNER} invoke [2]")),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_program_ids() {
+        assert_eq!(parse_invoke("Program log: invoke [1]"), None);
+        assert_eq!(parse_invoke_end("Program log: success"), None);
+        assert_eq!(parse_invoke_end("Program data: failed"), None);
+        assert_eq!(
+            parse_invoke(&format!("Program {OUTER} invoke [3]")),
+            Some((OUTER, 3))
+        );
+    }
+
+    #[test]
+    fn keeps_truncated_frames() {
+        let tree = parse_log_tree(&logs(&[
+            &format!("Program {OUTER} invoke [1]"),
+            &format!("Program {INNER} invoke [2]"),
+            "Log truncated",
+        ]));
+        assert_eq!(tree.len(), 1);
+        let outer = frame(&tree[0]);
+        assert_eq!(outer.result, None);
+        let inner = frame(&outer.entries[0]);
+        assert_eq!(inner.result, None);
+        assert_eq!(
+            inner.entries,
+            vec![LogEntry::Other("Log truncated".to_string())]
+        );
+    }
+}
@@ -0,0 +1,490 @@
+use crate::workspace::cheap_block_transaction::{
+    CheapBlockTransaction, ParsedVersionedTransactionWithStatusMeta,
+    VersionedTransactionWithStatusMeta,
+};
+use crate::workspace::transaction_encoding::JsonParsedInstructions;
+use anyhow::anyhow;
+use dioxus::logger::tracing::{error, info};
+use serde::{Deserialize, Serialize};
+use solana_transaction::versioned::TransactionVersion;
+use solana_transaction_status_client_types::UiTransactionStatusMeta;
+use std::rc::Rc;
+use utils::storage::binary_store::{BinaryStore, StoreEntry};
+use utils::time::now_timestamp_ms;
+
+const STORE_NAME: &str = "block_cache";
+/// 被跳过(没有区块)的slot，值为空
+const SKIPPED_STORE_NAME: &str = "skipped_slot_cache";
+/// 编码格式版本，格式不兼容时递增，旧版本数据会被视为不存在
+const FORMAT_VERSION: u8 = 4;
+
+/// 持久化缓存的淘汰策略
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PersistentBlockCacheConfig {
+    /// 最长保存时间(毫秒)
+    pub max_age_ms: i64,
+    /// 最多占用的存储空间(字节)
+    pub max_bytes: usize,
+}
+
+impl Default for PersistentBlockCacheConfig {
+    fn default() -> Self {
+        PersistentBlockCacheConfig {
+            max_age_ms: 7 * 24 * 60 * 60 * 1000,
+            max_bytes: 1024 * 1024 * 1024,
+        }
+    }
+}
+
+/// 落盘的区块，交易以解码后的形式保存，签名及帐号为二进制而非base58字符串
+#[derive(Serialize, Deserialize)]
+struct PersistedBlock {
+    format_version: u8,
+    slot: u64,
+    block_time: Option<i64>,
+    block_height: Option<u64>,
+    transactions: Vec<PersistedTransaction>,
+}
+
+/// 解码后的交易无法还原jsonParsed指令的解析结果，需单独保存，slot及出块时间与所在区块一致，不重复保存
+#[derive(Serialize, Deserialize)]
+struct PersistedTransaction {
+    /// bincode编码的交易(即链上的wire格式)，交易中的short_vec无法以MessagePack编码
+    #[serde(with = "serde_bytes")]
+    transaction: Vec<u8>,
+    meta: Option<UiTransactionStatusMeta>,
+    version: Option<TransactionVersion>,
+    json_parsed_instructions: JsonParsedInstructions,
+}
+
+/// 以MessagePack编码区块，结构体按字段名编码，元数据中省略的可选字段才能被正确还原。
+/// 区块中有未能解码的交易时返回错误，整个区块不会被保存，
+/// 以免重新加载后交易在区块中的下标与从服务端获取时不一致
+pub fn encode_block(block: &CheapBlockTransaction) -> anyhow::Result<Vec<u8>> {
+    let mut transactions = Vec::with_capacity(block.transactions.len());
+    for (index, tx) in block.transactions.iter().enumerate() {
+        let Some(transaction) = tx.transaction.transaction.as_ref() else {
+            return Err(anyhow!("区块中第{index}笔交易未能解码"));
+        };
+        transactions.push(PersistedTransaction {
+            transaction: bincode::serialize(transaction)?,
+            meta: tx.transaction.meta.clone(),
+            version: tx.transaction.version.clone(),
+            json_parsed_instructions: (*tx.json_parsed_instructions).clone(),
+        });
+    }
+    let persisted = PersistedBlock {
+        format_version: FORMAT_VERSION,
+        slot: block.slot,
+        block_time: block.block_time,
+        block_height: block.block_height,
+        transactions,
+    };
+    Ok(rmp_serde::to_vec_named(&persisted)?)
+}
+
+pub fn decode_block(data: &[u8]) -> anyhow::Result<CheapBlockTransaction> {
+    let persisted: PersistedBlock = rmp_serde::from_slice(data)?;
+    if persisted.format_version != FORMAT_VERSION {
+        return Err(anyhow!(
+            "不支持的缓存格式版本: {}",
+            persisted.format_version
+        ));
+    }
+    let mut transactions = Vec::with_capacity(persisted.transactions.len());
+    for tx in persisted.transactions {
+        let transaction = VersionedTransactionWithStatusMeta {
+            slot: persisted.slot,
+            transaction: Some(bincode::deserialize(&tx.transaction)?),
+            meta: tx.meta,
+            version: tx.version,
+            block_time: persisted.block_time,
+        };
+        transactions.extend(ParsedVersionedTransactionWithStatusMeta::from_versioned(
+            transaction,
+            tx.json_parsed_instructions,
+        ));
+    }
+    Ok(CheapBlockTransaction {
+        slot: persisted.slot,
+        block_time: persisted.block_time,
+        block_height: persisted.block_height,
+        transactions: Rc::new(transactions),
+    })
+}
+
+/// 已持久化的slot
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CachedSlot {
+    pub slot: u64,
+    pub saved_at_ms: i64,
+    pub size: usize,
+}
+
+impl From<StoreEntry> for CachedSlot {
+    fn from(entry: StoreEntry) -> Self {
+        CachedSlot {
+            slot: entry.key,
+            saved_at_ms: entry.saved_at_ms,
+            size: entry.size,
+        }
+    }
+}
+
+/// 保存在设备上的区块缓存，web端基于IndexedDB，原生端基于应用数据目录下的文件。
+/// 被跳过的slot单独保存，避免每次都向服务端请求
+pub struct PersistentBlockCache {
+    store: BinaryStore,
+    skipped: BinaryStore,
+    config: PersistentBlockCacheConfig,
+}
+
+impl PersistentBlockCache {
+    pub async fn open(config: PersistentBlockCacheConfig) -> anyhow::Result<Self> {
+        let store = BinaryStore::open(STORE_NAME).await?;
+        let skipped = BinaryStore::open(SKIPPED_STORE_NAME).await?;
+        let cache = PersistentBlockCache {
+            store,
+            skipped,
+            config,
+        };
+        cache.evict().await;
+        Ok(cache)
+    }
+
+    pub async fn save_block(&self, block: &CheapBlockTransaction) {
+        let ret = match encode_block(block) {
+            Ok(data) => self.store.put(block.slot, &data).await.map_err(|e| anyhow!(e)),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = ret {
+            error!("保存区块: {} 到本地缓存失败: {e}", block.slot);
+        }
+    }
+
+    pub async fn load_block(&self, slot: u64) -> Option<CheapBlockTransaction> {
+        let data = match self.store.get(slot).await {
+            Ok(data) => data?,
+            Err(e) => {
+                error!("读取本地缓存的区块: {slot} 失败: {e}");
+                return None;
+            }
+        };
+        match decode_block(&data) {
+            Ok(block) => Some(block),
+            Err(e) => {
+                error!("解码本地缓存的区块: {slot} 失败: {e}");
+                let _ = self.store.remove(slot).await;
+                None
+            }
+        }
+    }
+
+    /// 记录该slot没有区块
+    pub async fn save_skipped_slot(&self, slot: u64) {
+        if let Err(e) = self.skipped.put(slot, &[]).await {
+            error!("保存被跳过的slot: {slot} 到本地缓存失败: {e}");
+        }
+    }
+
+    /// 该slot是否已被记录为没有区块
+    pub async fn is_skipped_slot(&self, slot: u64) -> bool {
+        match self.skipped.get(slot).await {
+            Ok(data) => data.is_some(),
+            Err(e) => {
+                error!("读取本地缓存的被跳过的slot: {slot} 失败: {e}");
+                false
+            }
+        }
+    }
+
+    /// 所有已缓存的slot，按slot升序排列
+    pub async fn cached_slots(&self) -> Vec<CachedSlot> {
+        match self.store.entries().await {
+            Ok(entries) => {
+                let mut slots = entries.into_iter().map(CachedSlot::from).collect::<Vec<_>>();
+                slots.sort_by_key(|s| s.slot);
+                slots
+            }
+            Err(e) => {
+                error!("读取本地缓存列表失败: {e}");
+                Vec::new()
+            }
+        }
+    }
+
+    pub async fn remove(&self, slot: u64) {
+        if let Err(e) = self.store.remove(slot).await {
+            error!("删除本地缓存的区块: {slot} 失败: {e}");
+        }
+    }
+
+    /// 先淘汰过期的区块，再按保存时间从旧到新淘汰，直到总大小不超过限制，
+    /// 被跳过的slot只按保存时间淘汰
+    pub async fn evict(&self) {
+        self.evict_skipped_slots().await;
+        let mut entries = match self.store.entries().await {
+            Ok(entries) => entries,
+            Err(e) => {
+                error!("读取本地缓存列表失败: {e}");
+                return;
+            }
+        };
+        let now = now_timestamp_ms();
+        entries.sort_by_key(|e| e.saved_at_ms);
+        let mut total = entries.iter().map(|e| e.size).sum::<usize>();
+        let mut evicted = 0;
+        for entry in entries {
+            let expired = now - entry.saved_at_ms > self.config.max_age_ms;
+            if !expired && total <= self.config.max_bytes {
+                break;
+            }
+            self.remove(entry.key).await;
+            total -= entry.size;
+            evicted += 1;
+        }
+        if evicted > 0 {
+            info!("淘汰了{evicted}个本地缓存的区块");
+        }
+    }
+
+    async fn evict_skipped_slots(&self) {
+        let entries = match self.skipped.entries().await {
+            Ok(entries) => entries,
+            Err(e) => {
+                error!("读取本地缓存的被跳过的slot失败: {e}");
+                return;
+            }
+        };
+        let now = now_timestamp_ms();
+        for entry in entries {
+            if now - entry.saved_at_ms > self.config.max_age_ms
+                && let Err(e) = self.skipped.remove(entry.key).await
+            {
+                error!("删除本地缓存的被跳过的slot: {} 失败: {e}", entry.key);
+            }
+        }
+    }
+}
+
+/// 将已缓存的slot按连续性分组，相邻slot间隔不超过max_gap时视为同一段，用于浏览历史调查
+pub fn group_cached_slots(slots: &[CachedSlot], max_gap: u64) -> Vec<(u64, u64, usize)> {
+    let mut ranges: Vec<(u64, u64, usize)> = Vec::new();
+    for slot in slots {
+        match ranges.last_mut() {
+            Some((_, end, count)) if slot.slot - *end <= max_gap => {
+                *end = slot.slot;
+                *count += 1;
+            }
+            _ => ranges.push((slot.slot, slot.slot, 1)),
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::transaction_encoding::JsonParsedInstruction;
+    use serde_json::json;
+    use solana_hash::Hash;
+    use solana_message::compiled_instruction::CompiledInstruction;
+    use solana_message::{Message, VersionedMessage};
+    use solana_pubkey::Pubkey;
+    use solana_signature::Signature;
+    use solana_transaction::versioned::VersionedTransaction;
+    use solana_transaction_status_client_types::{
+        EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction,
+        EncodedTransactionWithStatusMeta, TransactionBinaryEncoding, UiTransactionStatusMeta,
+        option_serializer::OptionSerializer,
+    };
+
+    fn transaction(n: u8) -> ParsedVersionedTransactionWithStatusMeta {
+        transaction_with_meta(n, None)
+    }
+
+    fn transaction_with_meta(
+        n: u8,
+        meta: Option<UiTransactionStatusMeta>,
+    ) -> ParsedVersionedTransactionWithStatusMeta {
+        let payer = Pubkey::new_from_array([n; 32]);
+        let program = Pubkey::new_from_array([255; 32]);
+        let message = Message::new_with_compiled_instructions(
+            1,
+            0,
+            1,
+            vec![payer, program],
+            Hash::default(),
+            vec![CompiledInstruction::new_from_raw_parts(1, vec![n], vec![0])],
+        );
+        let transaction = VersionedTransaction {
+            signatures: vec![Signature::from([n; 64])],
+            message: VersionedMessage::Legacy(message),
+        };
+        ParsedVersionedTransactionWithStatusMeta::from_versioned(
+            VersionedTransactionWithStatusMeta {
+                slot: 100,
+                transaction: Some(transaction),
+                meta,
+                version: None,
+                block_time: Some(1_700_000_000),
+            },
+            JsonParsedInstructions::default(),
+        )
+        .unwrap()
+    }
+
+    fn block(transactions: Vec<ParsedVersionedTransactionWithStatusMeta>) -> CheapBlockTransaction {
+        CheapBlockTransaction {
+            slot: 100,
+            block_time: Some(1_700_000_000),
+            block_height: Some(90),
+            transactions: Rc::new(transactions),
+        }
+    }
+
+    #[test]
+    fn round_trip_keeps_transaction_order() {
+        let block = block(vec![transaction(1), transaction(2), transaction(3)]);
+        let decoded = decode_block(&encode_block(&block).unwrap()).unwrap();
+        assert_eq!(decoded.slot, block.slot);
+        assert_eq!(decoded.block_time, block.block_time);
+        assert_eq!(decoded.block_height, block.block_height);
+        let signatures = |b: &CheapBlockTransaction| {
+            b.transactions
+                .iter()
+                .map(|tx| tx.signature().copied())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(signatures(&decoded), signatures(&block));
+        assert_eq!(
+            decoded.transactions[1].transaction,
+            block.transactions[1].transaction
+        );
+    }
+
+    #[test]
+    fn round_trip_keeps_json_parsed_instructions() {
+        let mut tx = transaction(1);
+        let mut json_parsed = JsonParsedInstructions::default();
+        json_parsed.top_level.insert(
+            0,
+            JsonParsedInstruction {
+                program: "system".to_string(),
+                program_id: "11111111111111111111111111111111".to_string(),
+                parsed: json!({"type": "transfer", "info": {"lamports": 5000}}),
+                stack_height: None,
+            },
+        );
+        json_parsed.inner.insert(
+            (0, 1),
+            JsonParsedInstruction {
+                program: "spl-token".to_string(),
+                program_id: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string(),
+                parsed: json!({"type": "transferChecked"}),
+                stack_height: Some(2),
+            },
+        );
+        tx.json_parsed_instructions = Rc::new(json_parsed);
+        let block = block(vec![tx]);
+        let decoded = decode_block(&encode_block(&block).unwrap()).unwrap();
+        assert_eq!(
+            decoded.transactions[0].json_parsed_instructions,
+            block.transactions[0].json_parsed_instructions
+        );
+    }
+
+    #[test]
+    fn round_trip_keeps_meta() {
+        let meta = |optional: bool| {
+            let mut meta = json!({
+                "err": null,
+                "status": {"Ok": null},
+                "fee": 5000,
+                "preBalances": [1_000_000u64, 1],
+                "postBalances": [995_000u64, 1],
+                "innerInstructions": [
+                    {
+                        "index": 0,
+                        "instructions": [
+                            {"programIdIndex": 1, "accounts": [0], "data": "3Bxs4h24hBtQy9rw", "stackHeight": 2}
+                        ]
+                    }
+                ],
+                "logMessages": ["Program log: ok"],
+                "preTokenBalances": [],
+                "postTokenBalances": [],
+                "rewards": [],
+                "loadedAddresses": {"writable": [], "readonly": []}
+            });
+            if optional {
+                meta["returnData"] = json!({
+                    "programId": "11111111111111111111111111111111",
+                    "data": ["AQID", "base64"]
+                });
+                meta["computeUnitsConsumed"] = json!(1200);
+                meta["costUnits"] = json!(1500);
+            }
+            serde_json::from_value::<UiTransactionStatusMeta>(meta).unwrap()
+        };
+        let block = block(vec![
+            transaction_with_meta(1, Some(meta(true))),
+            transaction_with_meta(2, Some(meta(false))),
+        ]);
+        let decoded = decode_block(&encode_block(&block).unwrap()).unwrap();
+        for (decoded, original) in decoded.transactions.iter().zip(block.transactions.iter()) {
+            assert_eq!(decoded.transaction, original.transaction);
+        }
+        let meta = decoded.transactions[0].transaction.meta.as_ref().unwrap();
+        assert!(meta.return_data.is_some());
+        assert_eq!(meta.cost_units, OptionSerializer::Some(1500));
+        let meta = decoded.transactions[1].transaction.meta.as_ref().unwrap();
+        assert!(meta.return_data.is_skip());
+        assert!(meta.cost_units.is_skip());
+    }
+
+    #[test]
+    fn block_with_undecodable_transaction_is_not_encoded() {
+        let undecodable = ParsedVersionedTransactionWithStatusMeta::new(
+            EncodedConfirmedTransactionWithStatusMeta {
+                slot: 100,
+                transaction: EncodedTransactionWithStatusMeta {
+                    transaction: EncodedTransaction::Binary(
+                        "not base64".to_string(),
+                        TransactionBinaryEncoding::Base64,
+                    ),
+                    meta: None,
+                    version: None,
+                },
+                block_time: None,
+            },
+        );
+        assert!(undecodable.transaction.transaction.is_none());
+        let block = block(vec![transaction(1), undecodable, transaction(2)]);
+        assert!(encode_block(&block).is_err());
+    }
+
+    #[test]
+    fn rejects_other_format_versions() {
+        let mut data = encode_block(&block(vec![transaction(1)])).unwrap();
+        let mut persisted: PersistedBlock = rmp_serde::from_slice(&data).unwrap();
+        persisted.format_version = FORMAT_VERSION + 1;
+        data = rmp_serde::to_vec_named(&persisted).unwrap();
+        assert!(decode_block(&data).is_err());
+    }
+
+    #[test]
+    fn groups_nearby_slots() {
+        let slots = [10, 11, 15, 40]
+            .map(|slot| CachedSlot {
+                slot,
+                saved_at_ms: 0,
+                size: 1,
+            })
+            .to_vec();
+        assert_eq!(
+            group_cached_slots(&slots, 5),
+            vec![(10, 15, 3), (40, 40, 1)]
+        );
+    }
+}
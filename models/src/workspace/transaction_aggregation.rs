@@ -0,0 +1,120 @@
+use crate::workspace::cheap_block_transaction::FilteredBlockTransaction;
+use crate::workspace::transaction_filter::program_id_filter::{
+    ProgramInvocationScope, invoked_program_ids,
+};
+use std::collections::HashMap;
+
+/// CU直方图的分桶边界，最后一个桶不设上限
+pub const COMPUTE_UNIT_BUCKETS: [u64; 8] = [
+    0, 10_000, 50_000, 100_000, 200_000, 400_000, 800_000, 1_400_000,
+];
+
+/// 直方图中的一个桶，区间为[start, end)，end为None时不设上限
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct HistogramBucket {
+    pub start: u64,
+    pub end: Option<u64>,
+    pub count: usize,
+}
+
+/// 当前可见(已过滤)交易的汇总统计
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TransactionAggregation {
+    pub transaction_count: usize,
+    pub success_count: usize,
+    pub failure_count: usize,
+    /// 手续费总额(lamports)
+    pub total_fee: u64,
+    /// 手续费中位数(lamports)
+    pub median_fee: Option<u64>,
+    pub compute_unit_histogram: Vec<HistogramBucket>,
+    /// 每个slot的交易数，按区块顺序
+    pub transactions_per_slot: Vec<(u64, usize)>,
+    /// 被调用次数最多的程序(包含CPI)及调用它的交易数，降序
+    pub top_programs: Vec<(String, usize)>,
+    /// 交易数最多的签名者(手续费支付者)及其交易数，降序
+    pub top_signers: Vec<(String, usize)>,
+}
+
+impl TransactionAggregation {
+    /// 统计区块中通过过滤的交易，top_n为程序及签名者排行的条数
+    pub fn from_blocks(blocks: &[FilteredBlockTransaction], top_n: usize) -> Self {
+        let mut aggregation = TransactionAggregation {
+            compute_unit_histogram: COMPUTE_UNIT_BUCKETS
+                .iter()
+                .enumerate()
+                .map(|(i, &start)| HistogramBucket {
+                    start,
+                    end: COMPUTE_UNIT_BUCKETS.get(i + 1).copied(),
+                    count: 0,
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let mut fees = Vec::new();
+        let mut programs: HashMap<String, usize> = HashMap::new();
+        let mut signers: HashMap<String, usize> = HashMap::new();
+
+        for block in blocks {
+            aggregation
+                .transactions_per_slot
+                .push((block.slot(), block.len()));
+            for (_, tx) in block.iter() {
+                aggregation.transaction_count += 1;
+                match tx.is_success() {
+                    Some(true) => aggregation.success_count += 1,
+                    Some(false) => aggregation.failure_count += 1,
+                    None => {}
+                }
+                if let Some(fee) = tx.fee() {
+                    aggregation.total_fee += fee;
+                    fees.push(fee);
+                }
+                if let Some(cu) = tx.compute_units_consumed() {
+                    let bucket = COMPUTE_UNIT_BUCKETS
+                        .iter()
+                        .rposition(|&start| cu >= start)
+                        .unwrap_or(0);
+                    aggregation.compute_unit_histogram[bucket].count += 1;
+                }
+                if let Some(signer) = tx.fee_payer() {
+                    *signers.entry(signer.to_string()).or_default() += 1;
+                }
+                for program in invoked_program_ids(tx, ProgramInvocationScope::IncludingCpi) {
+                    *programs.entry(program).or_default() += 1;
+                }
+            }
+        }
+
+        aggregation.median_fee = median(&mut fees);
+        aggregation.top_programs = top(programs, top_n);
+        aggregation.top_signers = top(signers, top_n);
+        aggregation
+    }
+
+    /// 成功率，没有交易时为None
+    pub fn success_ratio(&self) -> Option<f64> {
+        let total = self.success_count + self.failure_count;
+        (total > 0).then(|| self.success_count as f64 / total as f64)
+    }
+}
+
+fn median(values: &mut [u64]) -> Option<u64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable();
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        Some((values[mid - 1] + values[mid]) / 2)
+    } else {
+        Some(values[mid])
+    }
+}
+
+fn top(counts: HashMap<String, usize>, n: usize) -> Vec<(String, usize)> {
+    let mut counts = counts.into_iter().collect::<Vec<_>>();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts.truncate(n);
+    counts
+}
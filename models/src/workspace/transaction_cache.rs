@@ -0,0 +1,463 @@
+use crate::workspace::cheap_block_transaction::{
+    CheapBlockTransaction, ParsedVersionedTransactionWithStatusMeta,
+};
+use solana_transaction_status_client_types::UiTransactionStatusMeta;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+/// 缓存数据占用内存的估算值(字节)
+pub trait CacheWeight {
+    fn weight(&self) -> usize;
+}
+
+struct LruEntry<V> {
+    value: V,
+    weight: usize,
+    tick: u64,
+}
+
+/// 同时限制条目数及总内存的LRU缓存，超出任一限制时淘汰最久未使用的条目
+pub struct LruCache<K, V> {
+    entries: HashMap<K, LruEntry<V>>,
+    /// 最近使用时间 -> 键，时间越小越久未使用
+    order: BTreeMap<u64, K>,
+    tick: u64,
+    weight: usize,
+    max_entries: usize,
+    max_weight: usize,
+}
+
+impl<K: Hash + Eq + Clone, V: CacheWeight> LruCache<K, V> {
+    pub fn new(max_entries: usize, max_weight: usize) -> Self {
+        LruCache {
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+            tick: 0,
+            weight: 0,
+            max_entries,
+            max_weight,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 当前所有条目占用内存的估算值
+    pub fn weight(&self) -> usize {
+        self.weight
+    }
+
+    /// 获取数据并将其标记为最近使用
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        let tick = self.next_tick();
+        let entry = self.entries.get_mut(key)?;
+        self.order.remove(&entry.tick);
+        self.order.insert(tick, key.clone());
+        entry.tick = tick;
+        Some(&entry.value)
+    }
+
+    /// 插入数据，单条数据超出内存限制时不缓存
+    pub fn insert(&mut self, key: K, value: V) {
+        self.remove(&key);
+        let weight = value.weight();
+        if weight > self.max_weight || self.max_entries == 0 {
+            return;
+        }
+        while !self.entries.is_empty()
+            && (self.entries.len() >= self.max_entries || self.weight + weight > self.max_weight)
+        {
+            self.evict_one();
+        }
+        let tick = self.next_tick();
+        self.order.insert(tick, key.clone());
+        self.weight += weight;
+        self.entries.insert(
+            key,
+            LruEntry {
+                value,
+                weight,
+                tick,
+            },
+        );
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let entry = self.entries.remove(key)?;
+        self.order.remove(&entry.tick);
+        self.weight -= entry.weight;
+        Some(entry.value)
+    }
+
+    /// 修改限制，超出新限制的条目会被立即淘汰
+    pub fn set_limits(&mut self, max_entries: usize, max_weight: usize) {
+        self.max_entries = max_entries;
+        self.max_weight = max_weight;
+        while !self.entries.is_empty()
+            && (self.entries.len() > self.max_entries || self.weight > self.max_weight)
+        {
+            self.evict_one();
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+        self.weight = 0;
+    }
+
+    fn evict_one(&mut self) {
+        if let Some((_, key)) = self.order.pop_first()
+            && let Some(entry) = self.entries.remove(&key)
+        {
+            self.weight -= entry.weight;
+        }
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+}
+
+/// 缓存配置
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TransactionCacheConfig {
+    /// 最多缓存的条目数(交易及区块)
+    pub max_entries: usize,
+    /// 内存预算(字节)
+    pub memory_budget: usize,
+}
+
+impl Default for TransactionCacheConfig {
+    fn default() -> Self {
+        TransactionCacheConfig {
+            max_entries: 2_000,
+            memory_budget: 512 * 1024 * 1024,
+        }
+    }
+}
+
+/// 缓存命中统计
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CacheStatistics {
+    pub hits: usize,
+    /// 内存中未命中、但在设备上的缓存中找到的次数，不计入misses
+    pub persistent_hits: usize,
+    pub misses: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum TransactionCacheKey {
+    Signature(String),
+    Slot(u64),
+}
+
+enum CachedData {
+    Transaction(ParsedVersionedTransactionWithStatusMeta),
+    Block(CheapBlockTransaction),
+    /// 该slot没有区块(被跳过)
+    SkippedSlot,
+}
+
+impl CacheWeight for CachedData {
+    fn weight(&self) -> usize {
+        match self {
+            CachedData::Transaction(tx) => tx.weight(),
+            CachedData::Block(block) => block.weight(),
+            CachedData::SkippedSlot => ENTRY_OVERHEAD,
+        }
+    }
+}
+
+/// 每个条目及每笔交易的固定开销估算
+const ENTRY_OVERHEAD: usize = 256;
+
+impl CacheWeight for ParsedVersionedTransactionWithStatusMeta {
+    fn weight(&self) -> usize {
+        let transaction = self
+            .transaction
+            .transaction
+            .as_ref()
+            .map(|tx| {
+                let message = &tx.message;
+                tx.signatures.len() * 64 * 2
+                    + message.static_account_keys().len() * 32 * 2
+                    + message
+                        .instructions()
+                        .iter()
+                        .map(|i| i.data.len() + i.accounts.len())
+                        .sum::<usize>()
+            })
+            .unwrap_or_default();
+        let meta = self.meta().map(meta_weight).unwrap_or_default();
+        ENTRY_OVERHEAD + transaction + meta + self.parsed_instructions.as_slice().len() * 64
+    }
+}
+
+fn meta_weight(meta: &UiTransactionStatusMeta) -> usize {
+    let logs = Option::<&Vec<String>>::from(meta.log_messages.as_ref())
+        .map(|logs| logs.iter().map(|l| l.len()).sum::<usize>())
+        .unwrap_or_default();
+    logs + (meta.pre_balances.len() + meta.post_balances.len()) * 8
+}
+
+impl CacheWeight for CheapBlockTransaction {
+    fn weight(&self) -> usize {
+        ENTRY_OVERHEAD + self.transactions.iter().map(|tx| tx.weight()).sum::<usize>()
+    }
+}
+
+/// 按签名缓存交易、按slot缓存区块的LRU缓存
+pub struct TransactionCache {
+    config: TransactionCacheConfig,
+    cache: LruCache<TransactionCacheKey, CachedData>,
+    statistics: CacheStatistics,
+}
+
+impl Default for TransactionCache {
+    fn default() -> Self {
+        Self::new(TransactionCacheConfig::default())
+    }
+}
+
+impl TransactionCache {
+    pub fn new(config: TransactionCacheConfig) -> Self {
+        TransactionCache {
+            config,
+            cache: LruCache::new(config.max_entries, config.memory_budget),
+            statistics: CacheStatistics::default(),
+        }
+    }
+
+    pub fn config(&self) -> TransactionCacheConfig {
+        self.config
+    }
+
+    pub fn set_config(&mut self, config: TransactionCacheConfig) {
+        self.config = config;
+        self.cache.set_limits(config.max_entries, config.memory_budget);
+    }
+
+    pub fn statistics(&self) -> CacheStatistics {
+        self.statistics
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// 缓存占用内存的估算值(字节)
+    pub fn weight(&self) -> usize {
+        self.cache.weight()
+    }
+
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    pub fn get_transaction(
+        &mut self,
+        signature: &str,
+    ) -> Option<ParsedVersionedTransactionWithStatusMeta> {
+        let key = TransactionCacheKey::Signature(signature.to_string());
+        let ret = match self.cache.get(&key) {
+            Some(CachedData::Transaction(tx)) => Some(tx.clone()),
+            _ => None,
+        };
+        self.record(ret.is_some());
+        ret
+    }
+
+    pub fn insert_transaction(&mut self, transaction: ParsedVersionedTransactionWithStatusMeta) {
+        if let Some(signature) = transaction.signature() {
+            let key = TransactionCacheKey::Signature(signature.to_string());
+            self.cache.insert(key, CachedData::Transaction(transaction));
+        }
+    }
+
+    pub fn insert_block(&mut self, block: CheapBlockTransaction) {
+        self.cache
+            .insert(TransactionCacheKey::Slot(block.slot), CachedData::Block(block));
+    }
+
+    /// 记录该slot没有区块，避免再次请求
+    pub fn insert_skipped_slot(&mut self, slot: u64) {
+        self.cache
+            .insert(TransactionCacheKey::Slot(slot), CachedData::SkippedSlot);
+    }
+
+    /// 查询[start_slot, end_slot]范围内已缓存的区块，返回已缓存的区块及缺失的连续slot区间
+    pub fn lookup_slots(
+        &mut self,
+        start_slot: u64,
+        end_slot: u64,
+    ) -> (Vec<CheapBlockTransaction>, Vec<(u64, u64)>) {
+        let mut blocks = Vec::new();
+        let mut missing: Vec<(u64, u64)> = Vec::new();
+        for slot in start_slot..=end_slot {
+            let hit = match self.cache.get(&TransactionCacheKey::Slot(slot)) {
+                Some(CachedData::Block(block)) => {
+                    blocks.push(block.clone());
+                    true
+                }
+                Some(CachedData::SkippedSlot) => true,
+                _ => false,
+            };
+            self.record(hit);
+            if !hit {
+                push_slot(&mut missing, slot);
+            }
+        }
+        (blocks, missing)
+    }
+
+    /// lookup_slots中未命中的slot在设备上的缓存中找到时调用，改记为设备缓存命中
+    pub fn record_persistent_hit(&mut self) {
+        self.statistics.misses = self.statistics.misses.saturating_sub(1);
+        self.statistics.persistent_hits += 1;
+    }
+
+    fn record(&mut self, hit: bool) {
+        if hit {
+            self.statistics.hits += 1;
+        } else {
+            self.statistics.misses += 1;
+        }
+    }
+}
+
+/// 将slot追加到按升序排列的连续区间列表中
+pub fn push_slot(ranges: &mut Vec<(u64, u64)>, slot: u64) {
+    match ranges.last_mut() {
+        Some((_, end)) if *end + 1 == slot => *end = slot,
+        _ => ranges.push((slot, slot)),
+    }
+}
+
+/// 查询交易附近区块时的slot窗口，与查询面板中"往回slot"/"往前slot"的含义一致，
+/// 为[slot - backward, slot + forward]，两端均包含
+pub fn nearby_slot_range(slot: u64, backward: Option<u32>, forward: Option<u32>) -> (u64, u64) {
+    (
+        slot.saturating_sub(backward.unwrap_or(0) as u64),
+        slot.saturating_add(forward.unwrap_or(0) as u64),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    impl CacheWeight for usize {
+        fn weight(&self) -> usize {
+            *self
+        }
+    }
+
+    fn block(slot: u64) -> CheapBlockTransaction {
+        CheapBlockTransaction {
+            slot,
+            block_time: None,
+            block_height: None,
+            transactions: Rc::new(Vec::new()),
+        }
+    }
+
+    #[test]
+    fn lru_evicts_least_recently_used() {
+        let mut cache = LruCache::new(2, usize::MAX);
+        cache.insert("a", 1);
+        cache.insert("b", 1);
+        assert!(cache.get(&"a").is_some());
+        cache.insert("c", 1);
+        assert!(cache.get(&"a").is_some());
+        assert!(cache.get(&"b").is_none());
+        assert!(cache.get(&"c").is_some());
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn lru_respects_weight_budget() {
+        let mut cache = LruCache::new(10, 10);
+        cache.insert("a", 4);
+        cache.insert("b", 4);
+        cache.insert("c", 4);
+        assert!(cache.get(&"a").is_none());
+        assert_eq!(cache.weight(), 8);
+        // 单条超出预算的数据不缓存
+        cache.insert("d", 11);
+        assert!(cache.get(&"d").is_none());
+        assert_eq!(cache.weight(), 8);
+        cache.set_limits(10, 4);
+        assert_eq!(cache.len(), 1);
+        assert!(cache.get(&"c").is_some());
+    }
+
+    #[test]
+    fn lru_replaces_existing_key() {
+        let mut cache = LruCache::new(10, 100);
+        cache.insert("a", 4);
+        cache.insert("a", 6);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.weight(), 6);
+        assert_eq!(cache.remove(&"a"), Some(6));
+        assert_eq!(cache.weight(), 0);
+    }
+
+    #[test]
+    fn lookup_splits_cached_and_missing_slots() {
+        let mut cache = TransactionCache::default();
+        cache.insert_block(block(11));
+        cache.insert_skipped_slot(12);
+        cache.insert_block(block(15));
+        let (blocks, missing) = cache.lookup_slots(10, 16);
+        assert_eq!(
+            blocks.iter().map(|b| b.slot).collect::<Vec<_>>(),
+            vec![11, 15]
+        );
+        assert_eq!(missing, vec![(10, 10), (13, 14), (16, 16)]);
+    }
+
+    #[test]
+    fn persistent_hits_are_not_counted_as_misses() {
+        let mut cache = TransactionCache::default();
+        cache.insert_block(block(11));
+        let (_, missing) = cache.lookup_slots(10, 12);
+        assert_eq!(missing, vec![(10, 10), (12, 12)]);
+        // slot 10在设备上的缓存中找到
+        cache.record_persistent_hit();
+        assert_eq!(
+            cache.statistics(),
+            CacheStatistics {
+                hits: 1,
+                persistent_hits: 1,
+                misses: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn push_slot_merges_consecutive_slots() {
+        let mut ranges = Vec::new();
+        for slot in [1, 2, 3, 5, 7, 8] {
+            push_slot(&mut ranges, slot);
+        }
+        assert_eq!(ranges, vec![(1, 3), (5, 5), (7, 8)]);
+    }
+
+    #[test]
+    fn nearby_range_saturates() {
+        assert_eq!(nearby_slot_range(100, Some(3), Some(2)), (97, 102));
+        assert_eq!(nearby_slot_range(1, Some(3), None), (0, 1));
+    }
+}
@@ -0,0 +1,398 @@
+use crate::workspace::balance_change::sol_balance_changes;
+use crate::workspace::cheap_block_transaction::ParsedVersionedTransactionWithStatusMeta;
+use crate::workspace::transaction_encoding::AccountKey;
+use block_insight_cross::parsed_instruction::ParsedInstruction;
+use block_insight_cross::transaction::transaction_filter::TransactionPropsProvider;
+use block_insight_cross::utils::TransactionAccounts;
+use solana_pubkey::Pubkey;
+
+/// 序列比较时，超过此规模(左右长度之积)不再计算最长公共子序列，直接按位置对齐
+const MAX_DIFF_CELLS: usize = 1_000_000;
+
+/// 两个序列对齐后的一行
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DiffRow {
+    /// 两侧相同的元素，分别为左右两侧的下标
+    Same(usize, usize),
+    /// 两侧位置对应但内容不同
+    Changed(usize, usize),
+    /// 只在左侧出现
+    LeftOnly(usize),
+    /// 只在右侧出现
+    RightOnly(usize),
+}
+
+/// 基于最长公共子序列对齐两个序列，相邻的删除与新增合并为修改
+pub fn diff_sequences<T: PartialEq>(left: &[T], right: &[T]) -> Vec<DiffRow> {
+    let (n, m) = (left.len(), right.len());
+    if n.saturating_mul(m) > MAX_DIFF_CELLS {
+        return (0..n.max(m))
+            .map(|i| match (i < n, i < m) {
+                (true, true) if left[i] == right[i] => DiffRow::Same(i, i),
+                (true, true) => DiffRow::Changed(i, i),
+                (true, false) => DiffRow::LeftOnly(i),
+                _ => DiffRow::RightOnly(i),
+            })
+            .collect();
+    }
+
+    // lcs[i][j]: left[i..]与right[j..]的最长公共子序列长度
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if left[i] == right[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut rows = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && left[i] == right[j] {
+            rows.push(DiffRow::Same(i, j));
+            i += 1;
+            j += 1;
+            continue;
+        }
+        // 收集到下一个相同元素之前的删除与新增，按位置两两配对为修改
+        let (start_i, start_j) = (i, j);
+        while i < n && j < m && left[i] != right[j] {
+            if lcs[i + 1][j] >= lcs[i][j + 1] {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        // 一侧已结束时，另一侧剩余的元素都不再有对应
+        if i == n {
+            j = m;
+        } else if j == m {
+            i = n;
+        }
+        let removed = start_i..i;
+        let added = start_j..j;
+        let paired = removed.len().min(added.len());
+        for k in 0..paired {
+            rows.push(DiffRow::Changed(start_i + k, start_j + k));
+        }
+        rows.extend((start_i + paired..i).map(DiffRow::LeftOnly));
+        rows.extend((start_j + paired..j).map(DiffRow::RightOnly));
+    }
+    rows
+}
+
+/// 一次程序调用，depth为CPI深度，顶层指令为0
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvocationStep {
+    pub depth: usize,
+    pub program_id: String,
+}
+
+/// 按执行顺序展开交易的所有程序调用(包括CPI)
+pub fn invocation_steps(transaction: &ParsedVersionedTransactionWithStatusMeta) -> Vec<InvocationStep> {
+    fn collect(
+        instruction: &ParsedInstruction,
+        accounts: &TransactionAccounts<'_, String>,
+        depth: usize,
+        steps: &mut Vec<InvocationStep>,
+    ) {
+        let program_id = accounts
+            .get(instruction.program_id_index as usize)
+            .cloned()
+            .unwrap_or_else(|| format!("#{}", instruction.program_id_index));
+        steps.push(InvocationStep { depth, program_id });
+        if let Some(inner) = instruction.inner_instructions.as_ref() {
+            for i in inner.iter() {
+                collect(i, accounts, depth + 1, steps);
+            }
+        }
+    }
+
+    let mut steps = Vec::new();
+    let accounts = transaction.get_accounts();
+    for i in transaction.parsed_instructions.iter() {
+        collect(i, &accounts, 0, &mut steps);
+    }
+    steps
+}
+
+/// 同一帐号在两笔交易中的权限及SOL余额变化，未出现的一侧为None
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccountComparison {
+    pub pubkey: Pubkey,
+    pub left: Option<(AccountKey, i128)>,
+    pub right: Option<(AccountKey, i128)>,
+}
+
+impl AccountComparison {
+    /// 两侧的权限或余额变化是否不同
+    pub fn is_different(&self) -> bool {
+        match (&self.left, &self.right) {
+            (Some((l, l_delta)), Some((r, r_delta))) => {
+                l.signer != r.signer || l.writable != r.writable || l_delta != r_delta
+            }
+            _ => true,
+        }
+    }
+}
+
+/// 同一所有者的同一种代币在两笔交易中的余额变化(最小单位)
+#[derive(Clone, Debug, PartialEq)]
+pub struct TokenComparison {
+    pub owner: Option<String>,
+    pub mint: String,
+    pub decimals: u8,
+    pub left: Option<i128>,
+    pub right: Option<i128>,
+}
+
+impl TokenComparison {
+    pub fn is_different(&self) -> bool {
+        self.left.unwrap_or(0) != self.right.unwrap_or(0)
+    }
+}
+
+/// 两笔交易的对比结果
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransactionComparison {
+    /// 按左侧帐号顺序排列，只在右侧出现的帐号排在最后
+    pub accounts: Vec<AccountComparison>,
+    pub left_steps: Vec<InvocationStep>,
+    pub right_steps: Vec<InvocationStep>,
+    pub steps: Vec<DiffRow>,
+    pub tokens: Vec<TokenComparison>,
+    pub left_logs: Vec<String>,
+    pub right_logs: Vec<String>,
+    pub logs: Vec<DiffRow>,
+}
+
+impl TransactionComparison {
+    pub fn new(
+        left: &ParsedVersionedTransactionWithStatusMeta,
+        right: &ParsedVersionedTransactionWithStatusMeta,
+    ) -> Self {
+        let left_steps = invocation_steps(left);
+        let right_steps = invocation_steps(right);
+        let steps = diff_sequences(&left_steps, &right_steps);
+        let left_logs = log_messages(left);
+        let right_logs = log_messages(right);
+        let logs = diff_sequences(&left_logs, &right_logs);
+        TransactionComparison {
+            accounts: compare_accounts(left, right),
+            left_steps,
+            right_steps,
+            steps,
+            tokens: compare_tokens(left, right),
+            left_logs,
+            right_logs,
+            logs,
+        }
+    }
+}
+
+/// 帐号及其SOL余额变化，没有元数据时余额变化为0
+fn accounts_with_delta(
+    transaction: &ParsedVersionedTransactionWithStatusMeta,
+) -> Vec<(AccountKey, i128)> {
+    let changes = sol_balance_changes(transaction);
+    if changes.is_empty() {
+        return transaction
+            .account_keys()
+            .into_iter()
+            .map(|k| (k, 0))
+            .collect();
+    }
+    changes
+        .into_iter()
+        .map(|c| {
+            let delta = c.delta();
+            (c.account, delta)
+        })
+        .collect()
+}
+
+fn compare_accounts(
+    left: &ParsedVersionedTransactionWithStatusMeta,
+    right: &ParsedVersionedTransactionWithStatusMeta,
+) -> Vec<AccountComparison> {
+    let mut right_accounts = accounts_with_delta(right)
+        .into_iter()
+        .map(Some)
+        .collect::<Vec<_>>();
+    let mut accounts = accounts_with_delta(left)
+        .into_iter()
+        .map(|l| {
+            let right = right_accounts
+                .iter_mut()
+                .find(|r| r.as_ref().is_some_and(|(k, _)| k.pubkey == l.0.pubkey))
+                .and_then(|r| r.take());
+            AccountComparison {
+                pubkey: l.0.pubkey,
+                left: Some(l),
+                right,
+            }
+        })
+        .collect::<Vec<_>>();
+    accounts.extend(right_accounts.into_iter().flatten().map(|r| AccountComparison {
+        pubkey: r.0.pubkey,
+        left: None,
+        right: Some(r),
+    }));
+    accounts
+}
+
+fn compare_tokens(
+    left: &ParsedVersionedTransactionWithStatusMeta,
+    right: &ParsedVersionedTransactionWithStatusMeta,
+) -> Vec<TokenComparison> {
+    let mut tokens: Vec<TokenComparison> = Vec::new();
+    for (change, is_left) in left
+        .token_balance_changes()
+        .iter()
+        .map(|c| (c, true))
+        .chain(right.token_balance_changes().iter().map(|c| (c, false)))
+    {
+        let index = tokens
+            .iter()
+            .position(|t| t.owner == change.owner && t.mint == change.mint);
+        let token = match index {
+            Some(index) => &mut tokens[index],
+            None => {
+                tokens.push(TokenComparison {
+                    owner: change.owner.clone(),
+                    mint: change.mint.clone(),
+                    decimals: change.decimals,
+                    left: None,
+                    right: None,
+                });
+                tokens.last_mut().expect("刚刚插入")
+            }
+        };
+        if is_left {
+            token.left = Some(change.delta());
+        } else {
+            token.right = Some(change.delta());
+        }
+    }
+    tokens
+}
+
+fn log_messages(transaction: &ParsedVersionedTransactionWithStatusMeta) -> Vec<String> {
+    transaction
+        .meta()
+        .and_then(|m| Option::<&Vec<String>>::from(m.log_messages.as_ref()))
+        .cloned()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_sequences_are_same() {
+        assert_eq!(
+            diff_sequences(&[1, 2, 3], &[1, 2, 3]),
+            vec![
+                DiffRow::Same(0, 0),
+                DiffRow::Same(1, 1),
+                DiffRow::Same(2, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn aligns_around_insertions_and_removals() {
+        assert_eq!(
+            diff_sequences(&["a", "b", "c"], &["a", "x", "b", "c", "d"]),
+            vec![
+                DiffRow::Same(0, 0),
+                DiffRow::RightOnly(1),
+                DiffRow::Same(1, 2),
+                DiffRow::Same(2, 3),
+                DiffRow::RightOnly(4),
+            ]
+        );
+        assert_eq!(
+            diff_sequences(&["a", "b", "c"], &["c"]),
+            vec![
+                DiffRow::LeftOnly(0),
+                DiffRow::LeftOnly(1),
+                DiffRow::Same(2, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn pairs_adjacent_removal_and_insertion_as_changed() {
+        assert_eq!(
+            diff_sequences(&["a", "b", "c"], &["a", "x", "c"]),
+            vec![
+                DiffRow::Same(0, 0),
+                DiffRow::Changed(1, 1),
+                DiffRow::Same(2, 2)
+            ]
+        );
+        assert_eq!(
+            diff_sequences(&["a", "b", "c"], &["x", "y"]),
+            vec![
+                DiffRow::Changed(0, 0),
+                DiffRow::Changed(1, 1),
+                DiffRow::LeftOnly(2),
+            ]
+        );
+    }
+
+    #[test]
+    fn handles_empty_sequences() {
+        assert_eq!(diff_sequences::<u8>(&[], &[]), vec![]);
+        assert_eq!(
+            diff_sequences(&[], &[1, 2]),
+            vec![DiffRow::RightOnly(0), DiffRow::RightOnly(1)]
+        );
+        assert_eq!(diff_sequences(&[1], &[]), vec![DiffRow::LeftOnly(0)]);
+    }
+
+    #[test]
+    fn every_element_appears_once_in_order() {
+        let left = [1, 3, 5, 7, 9, 2, 4];
+        let right = [3, 4, 5, 9, 8, 2, 1];
+        let rows = diff_sequences(&left, &right);
+        let left_indices = rows
+            .iter()
+            .filter_map(|r| match *r {
+                DiffRow::Same(i, _) | DiffRow::Changed(i, _) | DiffRow::LeftOnly(i) => Some(i),
+                DiffRow::RightOnly(_) => None,
+            })
+            .collect::<Vec<_>>();
+        let right_indices = rows
+            .iter()
+            .filter_map(|r| match *r {
+                DiffRow::Same(_, j) | DiffRow::Changed(_, j) | DiffRow::RightOnly(j) => Some(j),
+                DiffRow::LeftOnly(_) => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(left_indices, (0..left.len()).collect::<Vec<_>>());
+        assert_eq!(right_indices, (0..right.len()).collect::<Vec<_>>());
+        for row in rows {
+            if let DiffRow::Same(i, j) = row {
+                assert_eq!(left[i], right[j]);
+            }
+        }
+    }
+
+    #[test]
+    fn large_sequences_fall_back_to_positional_alignment() {
+        let left = vec![0u8; 1001];
+        let mut right = vec![0u8; 1001];
+        right[1] = 1;
+        right.push(2);
+        let rows = diff_sequences(&left, &right);
+        assert_eq!(rows.len(), 1002);
+        assert_eq!(rows[1], DiffRow::Changed(1, 1));
+        assert_eq!(rows[1001], DiffRow::RightOnly(1001));
+    }
+}
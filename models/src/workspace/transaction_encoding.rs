@@ -0,0 +1,645 @@
+use anyhow::anyhow;
+use dioxus::logger::tracing::error;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use solana_hash::Hash;
+use solana_message::compiled_instruction::CompiledInstruction;
+use solana_message::v0::MessageAddressTableLookup;
+use solana_message::{Message, MessageHeader, VersionedMessage, v0};
+use solana_pubkey::Pubkey;
+use solana_signature::Signature;
+use solana_transaction::versioned::VersionedTransaction;
+use solana_transaction_status_client_types::{
+    EncodedTransaction, EncodedTransactionWithStatusMeta, ParsedAccount, ParsedAccountSource,
+    ParsedInstruction, UiAddressTableLookup, UiCompiledInstruction, UiInnerInstructions,
+    UiInstruction, UiLoadedAddresses, UiMessage, UiParsedInstruction, UiParsedMessage,
+    UiPartiallyDecodedInstruction, UiRawMessage, UiTransaction, UiTransactionStatusMeta,
+};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// jsonParsed编码中已被服务端完全解析的指令，这类指令不再包含原始数据，只保留解析结果。
+/// 还原后的Message中，同一位置的指令只有程序下标是真实的，帐号及数据为空，展示或导出时应当使用此处的解析结果
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JsonParsedInstruction {
+    /// 程序名，如: system, spl-token
+    pub program: String,
+    pub program_id: String,
+    pub parsed: Value,
+    pub stack_height: Option<u32>,
+}
+
+impl JsonParsedInstruction {
+    /// 指令类型，如: transfer
+    pub fn instruction_type(&self) -> Option<&str> {
+        self.parsed.get("type").and_then(|t| t.as_str())
+    }
+}
+
+/// 按指令位置索引的jsonParsed指令
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct JsonParsedInstructions {
+    /// 顶层指令下标 -> 指令
+    pub top_level: HashMap<usize, JsonParsedInstruction>,
+    /// (顶层指令下标, 内部指令下标) -> 指令
+    pub inner: HashMap<(usize, usize), JsonParsedInstruction>,
+}
+
+impl JsonParsedInstructions {
+    pub fn is_empty(&self) -> bool {
+        self.top_level.is_empty() && self.inner.is_empty()
+    }
+
+    /// 指定位置的指令是否只有jsonParsed解析结果，inner_index为None时表示顶层指令
+    pub fn get(
+        &self,
+        top_index: usize,
+        inner_index: Option<usize>,
+    ) -> Option<&JsonParsedInstruction> {
+        match inner_index {
+            None => self.top_level.get(&top_index),
+            Some(i) => self.inner.get(&(top_index, i)),
+        }
+    }
+}
+
+/// 帐号及其在交易中的权限
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccountKey {
+    pub pubkey: Pubkey,
+    pub signer: bool,
+    pub writable: bool,
+}
+
+/// 将交易统一转换为Json编码且Message为Raw格式:
+/// base58/base64编码的交易会被解码后重新以Raw格式表示,
+/// jsonParsed编码的交易会根据帐号列表还原Message头、指令中的帐号下标以及通过地址表加载的地址,
+/// 已被服务端完全解析的指令没有原始数据，其解析结果保存在返回的[JsonParsedInstructions]中。
+/// 指令引用了帐号列表之外的帐号时无法还原，保留原始的jsonParsed格式，该交易将无法解码
+pub fn normalize_encoded_transaction(
+    transaction: EncodedTransactionWithStatusMeta,
+) -> (EncodedTransactionWithStatusMeta, JsonParsedInstructions) {
+    let EncodedTransactionWithStatusMeta {
+        transaction,
+        mut meta,
+        version,
+    } = transaction;
+    let mut json_parsed = JsonParsedInstructions::default();
+    let transaction = match transaction {
+        EncodedTransaction::Json(UiTransaction {
+            signatures,
+            message: UiMessage::Parsed(message),
+        }) => match normalize_parsed_message(&message, meta.as_mut()) {
+            Ok((raw, parsed)) => {
+                json_parsed = parsed;
+                EncodedTransaction::Json(UiTransaction {
+                    signatures,
+                    message: UiMessage::Raw(raw),
+                })
+            }
+            Err(e) => {
+                error!("还原jsonParsed交易失败: {e:#}");
+                EncodedTransaction::Json(UiTransaction {
+                    signatures,
+                    message: UiMessage::Parsed(message),
+                })
+            }
+        },
+        EncodedTransaction::Json(_) => transaction,
+        EncodedTransaction::LegacyBinary(_) | EncodedTransaction::Binary(_, _) => {
+            match transaction.decode() {
+                Some(decoded) => EncodedTransaction::Json(versioned_transaction_to_ui(&decoded)),
+                None => {
+                    error!("解码二进制交易数据失败");
+                    transaction
+                }
+            }
+        }
+        EncodedTransaction::Accounts(_) => {
+            error!("交易只包含帐号列表，无法解析签名及指令");
+            transaction
+        }
+    };
+    let transaction = EncodedTransactionWithStatusMeta {
+        transaction,
+        meta,
+        version,
+    };
+    (transaction, json_parsed)
+}
+
+/// 将解码后的交易以Raw格式的Message表示
+pub fn versioned_transaction_to_ui(transaction: &VersionedTransaction) -> UiTransaction {
+    let message = &transaction.message;
+    let raw = UiRawMessage {
+        header: *message.header(),
+        account_keys: message
+            .static_account_keys()
+            .iter()
+            .map(|k| k.to_string())
+            .collect(),
+        recent_blockhash: message.recent_blockhash().to_string(),
+        instructions: message
+            .instructions()
+            .iter()
+            .map(|i| UiCompiledInstruction::from(i, None))
+            .collect(),
+        address_table_lookups: message
+            .address_table_lookups()
+            .map(|lookups| lookups.iter().map(UiAddressTableLookup::from).collect()),
+    };
+    UiTransaction {
+        signatures: transaction
+            .signatures
+            .iter()
+            .map(|s| s.to_string())
+            .collect(),
+        message: UiMessage::Raw(raw),
+    }
+}
+
+/// 将解码后的交易以jsonParsed格式的Message表示，[JsonParsedInstructions]中的指令(包括元数据中的内部指令)
+/// 以解析结果放回原位置，其余指令为部分解码的格式，结果可再次被[normalize_encoded_transaction]还原
+pub fn versioned_transaction_to_parsed_ui(
+    transaction: &VersionedTransaction,
+    meta: Option<&mut UiTransactionStatusMeta>,
+    json_parsed: &JsonParsedInstructions,
+) -> UiTransaction {
+    let message = &transaction.message;
+    let num_static = message.static_account_keys().len();
+    let loaded_addresses = meta
+        .as_deref()
+        .and_then(|m| Option::<&UiLoadedAddresses>::from(m.loaded_addresses.as_ref()));
+    let account_keys = message_account_keys(message, loaded_addresses)
+        .into_iter()
+        .enumerate()
+        .map(|(i, key)| ParsedAccount {
+            pubkey: key.pubkey.to_string(),
+            writable: key.writable,
+            signer: key.signer,
+            source: Some(if i < num_static {
+                ParsedAccountSource::Transaction
+            } else {
+                ParsedAccountSource::LookupTable
+            }),
+        })
+        .collect::<Vec<_>>();
+    let pubkeys = account_keys
+        .iter()
+        .map(|k| k.pubkey.as_str())
+        .collect::<Vec<_>>();
+    let instructions = message
+        .instructions()
+        .iter()
+        .enumerate()
+        .map(|(i, instruction)| {
+            let compiled = UiCompiledInstruction::from(instruction, None);
+            to_parsed_instruction(&compiled, json_parsed.get(i, None), &pubkeys)
+        })
+        .collect();
+    if let Some(meta) = meta {
+        let all: Option<&mut Vec<UiInnerInstructions>> = meta.inner_instructions.as_mut().into();
+        for inner in all.into_iter().flatten() {
+            let index = inner.index as usize;
+            for (i, instruction) in inner.instructions.iter_mut().enumerate() {
+                if let UiInstruction::Compiled(compiled) = instruction {
+                    *instruction =
+                        to_parsed_instruction(compiled, json_parsed.get(index, Some(i)), &pubkeys);
+                }
+            }
+        }
+    }
+    UiTransaction {
+        signatures: transaction
+            .signatures
+            .iter()
+            .map(|s| s.to_string())
+            .collect(),
+        message: UiMessage::Parsed(UiParsedMessage {
+            account_keys,
+            recent_blockhash: message.recent_blockhash().to_string(),
+            instructions,
+            address_table_lookups: message
+                .address_table_lookups()
+                .map(|lookups| lookups.iter().map(UiAddressTableLookup::from).collect()),
+        }),
+    }
+}
+
+/// 有解析结果时使用解析结果，否则以帐号地址表示为部分解码的指令
+fn to_parsed_instruction(
+    compiled: &UiCompiledInstruction,
+    parsed: Option<&JsonParsedInstruction>,
+    pubkeys: &[&str],
+) -> UiInstruction {
+    let pubkey = |index: u8| {
+        pubkeys
+            .get(index as usize)
+            .copied()
+            .unwrap_or_default()
+            .to_string()
+    };
+    let instruction = match parsed {
+        Some(parsed) => UiParsedInstruction::Parsed(ParsedInstruction {
+            program: parsed.program.clone(),
+            program_id: parsed.program_id.clone(),
+            parsed: parsed.parsed.clone(),
+            stack_height: parsed.stack_height,
+        }),
+        None => UiParsedInstruction::PartiallyDecoded(UiPartiallyDecodedInstruction {
+            program_id: pubkey(compiled.program_id_index),
+            accounts: compiled.accounts.iter().map(|a| pubkey(*a)).collect(),
+            data: compiled.data.clone(),
+            stack_height: compiled.stack_height,
+        }),
+    };
+    UiInstruction::Parsed(instruction)
+}
+
+/// 将Raw格式的Json交易解码为[VersionedTransaction]，存在地址表查询时解码为v0格式，否则为legacy格式
+pub fn ui_transaction_to_versioned(transaction: &UiTransaction) -> anyhow::Result<VersionedTransaction> {
+    let UiMessage::Raw(message) = &transaction.message else {
+        return Err(anyhow!("只支持Raw格式的Message"));
+    };
+    let signatures = transaction
+        .signatures
+        .iter()
+        .map(|s| Signature::from_str(s))
+        .collect::<Result<Vec<_>, _>>()?;
+    let account_keys = message
+        .account_keys
+        .iter()
+        .map(|k| Pubkey::from_str(k))
+        .collect::<Result<Vec<_>, _>>()?;
+    let recent_blockhash = Hash::from_str(&message.recent_blockhash)?;
+    let instructions = message
+        .instructions
+        .iter()
+        .map(|i| {
+            Ok(CompiledInstruction {
+                program_id_index: i.program_id_index,
+                accounts: i.accounts.clone(),
+                data: bs58::decode(&i.data).into_vec()?,
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let message = match &message.address_table_lookups {
+        Some(lookups) => {
+            let address_table_lookups = lookups
+                .iter()
+                .map(|l| {
+                    Ok(MessageAddressTableLookup {
+                        account_key: Pubkey::from_str(&l.account_key)?,
+                        writable_indexes: l.writable_indexes.clone(),
+                        readonly_indexes: l.readonly_indexes.clone(),
+                    })
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            VersionedMessage::V0(v0::Message {
+                header: message.header,
+                account_keys,
+                recent_blockhash,
+                instructions,
+                address_table_lookups,
+            })
+        }
+        None => VersionedMessage::Legacy(Message {
+            header: message.header,
+            account_keys,
+            recent_blockhash,
+            instructions,
+        }),
+    };
+    Ok(VersionedTransaction {
+        signatures,
+        message,
+    })
+}
+
+/// Message中所有帐号(包括通过地址表加载的)及其权限，顺序与指令中的帐号下标一致
+pub fn message_account_keys(
+    message: &VersionedMessage,
+    loaded_addresses: Option<&UiLoadedAddresses>,
+) -> Vec<AccountKey> {
+    let header = message.header();
+    let static_keys = message.static_account_keys();
+    let total = static_keys.len();
+    let num_signed = header.num_required_signatures as usize;
+    let num_writable_signed = num_signed.saturating_sub(header.num_readonly_signed_accounts as usize);
+    let num_writable_unsigned = total
+        .saturating_sub(num_signed)
+        .saturating_sub(header.num_readonly_unsigned_accounts as usize);
+    let mut keys = static_keys
+        .iter()
+        .enumerate()
+        .map(|(i, pubkey)| {
+            let signer = i < num_signed;
+            let writable = if signer {
+                i < num_writable_signed
+            } else {
+                i - num_signed < num_writable_unsigned
+            };
+            AccountKey {
+                pubkey: *pubkey,
+                signer,
+                writable,
+            }
+        })
+        .collect::<Vec<_>>();
+    if let Some(loaded) = loaded_addresses {
+        let loaded_keys = |addresses: &[String], writable: bool| {
+            addresses
+                .iter()
+                .filter_map(|pubkey| match Pubkey::from_str(pubkey) {
+                    Ok(pubkey) => Some(pubkey),
+                    Err(e) => {
+                        error!("地址表加载的地址: {pubkey} 格式错误: {e}");
+                        None
+                    }
+                })
+                .map(move |pubkey| AccountKey {
+                    pubkey,
+                    signer: false,
+                    writable,
+                })
+                .collect::<Vec<_>>()
+        };
+        keys.extend(loaded_keys(&loaded.writable, true));
+        keys.extend(loaded_keys(&loaded.readonly, false));
+    }
+    keys
+}
+
+/// 还原jsonParsed格式的Message及元数据中的内部指令，任一指令无法还原时返回错误，元数据保持不变
+fn normalize_parsed_message(
+    message: &UiParsedMessage,
+    meta: Option<&mut UiTransactionStatusMeta>,
+) -> anyhow::Result<(UiRawMessage, JsonParsedInstructions)> {
+    let mut json_parsed = JsonParsedInstructions::default();
+    let account_keys = message
+        .account_keys
+        .iter()
+        .map(|k| k.pubkey.clone())
+        .collect::<Vec<_>>();
+    let (raw, loaded_addresses) =
+        parsed_message_to_raw(message, &account_keys, &mut json_parsed)?;
+    if let Some(meta) = meta {
+        normalize_meta(meta, &account_keys, loaded_addresses, &mut json_parsed)?;
+    }
+    Ok((raw, json_parsed))
+}
+
+/// 返回Raw格式的Message以及通过地址表加载的地址
+fn parsed_message_to_raw(
+    message: &UiParsedMessage,
+    all_keys: &[String],
+    json_parsed: &mut JsonParsedInstructions,
+) -> anyhow::Result<(UiRawMessage, Option<UiLoadedAddresses>)> {
+    let mut static_keys = Vec::new();
+    let mut loaded = UiLoadedAddresses {
+        writable: Vec::new(),
+        readonly: Vec::new(),
+    };
+    let mut header = MessageHeader::default();
+    for key in &message.account_keys {
+        if matches!(key.source, Some(ParsedAccountSource::LookupTable)) {
+            if key.writable {
+                loaded.writable.push(key.pubkey.clone());
+            } else {
+                loaded.readonly.push(key.pubkey.clone());
+            }
+            continue;
+        }
+        if key.signer {
+            header.num_required_signatures += 1;
+            if !key.writable {
+                header.num_readonly_signed_accounts += 1;
+            }
+        } else if !key.writable {
+            header.num_readonly_unsigned_accounts += 1;
+        }
+        static_keys.push(key.pubkey.clone());
+    }
+
+    let mut compiled_instructions = Vec::with_capacity(message.instructions.len());
+    for (i, instruction) in message.instructions.iter().enumerate() {
+        let (compiled, parsed) = to_compiled_instruction(instruction, all_keys)
+            .map_err(|e| anyhow!("第{i}条指令: {e}"))?;
+        if let Some(parsed) = parsed {
+            json_parsed.top_level.insert(i, parsed);
+        }
+        compiled_instructions.push(compiled);
+    }
+
+    let has_loaded = !loaded.writable.is_empty() || !loaded.readonly.is_empty();
+    let raw = UiRawMessage {
+        header,
+        account_keys: static_keys,
+        recent_blockhash: message.recent_blockhash.clone(),
+        instructions: compiled_instructions,
+        address_table_lookups: message.address_table_lookups.clone(),
+    };
+    Ok((raw, has_loaded.then_some(loaded)))
+}
+
+/// jsonParsed编码的元数据中，内部指令同样为解析后的格式，地址表加载的地址则被并入了帐号列表。
+/// 内部指令全部还原成功后才会修改元数据
+fn normalize_meta(
+    meta: &mut UiTransactionStatusMeta,
+    account_keys: &[String],
+    loaded_addresses: Option<UiLoadedAddresses>,
+    json_parsed: &mut JsonParsedInstructions,
+) -> anyhow::Result<()> {
+    let all: Option<&Vec<UiInnerInstructions>> = meta.inner_instructions.as_ref().into();
+    if let Some(all) = all {
+        let mut inner_instructions = Vec::with_capacity(all.len());
+        for inner in all {
+            let index = inner.index as usize;
+            let mut instructions = Vec::with_capacity(inner.instructions.len());
+            for (i, instruction) in inner.instructions.iter().enumerate() {
+                let (compiled, parsed) = to_compiled_instruction(instruction, account_keys)
+                    .map_err(|e| anyhow!("第{index}条指令的第{i}条内部指令: {e}"))?;
+                if let Some(parsed) = parsed {
+                    json_parsed.inner.insert((index, i), parsed);
+                }
+                instructions.push(UiInstruction::Compiled(compiled));
+            }
+            inner_instructions.push(UiInnerInstructions {
+                index: inner.index,
+                instructions,
+            });
+        }
+        meta.inner_instructions = Some(inner_instructions).into();
+    }
+
+    let has_loaded_addresses = Option::<&UiLoadedAddresses>::from(meta.loaded_addresses.as_ref())
+        .is_some_and(|l| !l.writable.is_empty() || !l.readonly.is_empty());
+    if !has_loaded_addresses
+        && let Some(loaded) = loaded_addresses
+    {
+        meta.loaded_addresses = Some(loaded).into();
+    }
+    Ok(())
+}
+
+/// 根据帐号列表还原指令中的帐号下标，程序或帐号不在帐号列表中时返回错误，以免指令被归属到错误的程序。
+/// 已被完全解析的指令没有原始数据，返回的指令只有程序下标，帐号及数据为空，解析结果一并返回
+fn to_compiled_instruction(
+    instruction: &UiInstruction,
+    account_keys: &[String],
+) -> anyhow::Result<(UiCompiledInstruction, Option<JsonParsedInstruction>)> {
+    let index_of = |pubkey: &str| {
+        account_keys
+            .iter()
+            .position(|k| k == pubkey)
+            .map(|i| i as u8)
+            .ok_or_else(|| anyhow!("帐号: {pubkey} 不在交易的帐号列表中"))
+    };
+    let ret = match instruction {
+        UiInstruction::Compiled(compiled) => (compiled.clone(), None),
+        UiInstruction::Parsed(UiParsedInstruction::PartiallyDecoded(decoded)) => {
+            let compiled = UiCompiledInstruction {
+                program_id_index: index_of(&decoded.program_id)?,
+                accounts: decoded
+                    .accounts
+                    .iter()
+                    .map(|a| index_of(a))
+                    .collect::<anyhow::Result<Vec<_>>>()?,
+                data: decoded.data.clone(),
+                stack_height: decoded.stack_height,
+            };
+            (compiled, None)
+        }
+        UiInstruction::Parsed(UiParsedInstruction::Parsed(parsed)) => {
+            let compiled = UiCompiledInstruction {
+                program_id_index: index_of(&parsed.program_id)?,
+                accounts: Vec::new(),
+                data: String::new(),
+                stack_height: parsed.stack_height,
+            };
+            let parsed = JsonParsedInstruction {
+                program: parsed.program.clone(),
+                program_id: parsed.program_id.clone(),
+                parsed: parsed.parsed.clone(),
+                stack_height: parsed.stack_height,
+            };
+            (compiled, Some(parsed))
+        }
+    };
+    Ok(ret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_transaction_status_client_types::{
+        ParsedAccount, ParsedInstruction, UiPartiallyDecodedInstruction,
+    };
+
+    fn partially_decoded(program_id: &str, accounts: &[&str]) -> UiInstruction {
+        UiInstruction::Parsed(UiParsedInstruction::PartiallyDecoded(
+            UiPartiallyDecodedInstruction {
+                program_id: program_id.to_string(),
+                accounts: accounts.iter().map(|a| a.to_string()).collect(),
+                data: String::new(),
+                stack_height: None,
+            },
+        ))
+    }
+
+    #[test]
+    fn resolves_account_indices() {
+        let keys = ["payer", "account", "program"].map(String::from);
+        let (compiled, parsed) =
+            to_compiled_instruction(&partially_decoded("program", &["account", "payer"]), &keys)
+                .unwrap();
+        assert_eq!(compiled.program_id_index, 2);
+        assert_eq!(compiled.accounts, vec![1, 0]);
+        assert!(parsed.is_none());
+    }
+
+    fn parsed_transaction(instructions: Vec<UiInstruction>) -> EncodedTransactionWithStatusMeta {
+        let account = |pubkey: &str, signer: bool, writable: bool| ParsedAccount {
+            pubkey: pubkey.to_string(),
+            writable,
+            signer,
+            source: Some(ParsedAccountSource::Transaction),
+        };
+        EncodedTransactionWithStatusMeta {
+            transaction: EncodedTransaction::Json(UiTransaction {
+                signatures: vec![Signature::default().to_string()],
+                message: UiMessage::Parsed(UiParsedMessage {
+                    account_keys: vec![
+                        account(PAYER, true, true),
+                        account(RECEIVER, false, true),
+                        account(SYSTEM_PROGRAM, false, false),
+                    ],
+                    recent_blockhash: Hash::default().to_string(),
+                    instructions,
+                    address_table_lookups: None,
+                }),
+            }),
+            meta: None,
+            version: None,
+        }
+    }
+
+    fn system_transfer() -> UiInstruction {
+        UiInstruction::Parsed(UiParsedInstruction::Parsed(ParsedInstruction {
+            program: "system".to_string(),
+            program_id: SYSTEM_PROGRAM.to_string(),
+            parsed: serde_json::json!({"type": "transfer"}),
+            stack_height: None,
+        }))
+    }
+
+    const PAYER: &str = "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU";
+    const RECEIVER: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+    const SYSTEM_PROGRAM: &str = "11111111111111111111111111111111";
+
+    #[test]
+    fn keeps_positions_of_json_parsed_instructions() {
+        let (normalized, json_parsed) = normalize_encoded_transaction(parsed_transaction(vec![
+            system_transfer(),
+            partially_decoded(SYSTEM_PROGRAM, &[PAYER, RECEIVER]),
+        ]));
+        let EncodedTransaction::Json(UiTransaction {
+            message: UiMessage::Raw(raw),
+            ..
+        }) = normalized.transaction
+        else {
+            panic!("应当还原为Raw格式");
+        };
+        assert_eq!(raw.instructions.len(), 2);
+        assert_eq!(raw.instructions[0].program_id_index, 2);
+        assert_eq!(raw.instructions[1].accounts, vec![0, 1]);
+        assert_eq!(
+            json_parsed.get(0, None).map(|p| p.program_id.as_str()),
+            Some(SYSTEM_PROGRAM)
+        );
+        assert!(json_parsed.get(1, None).is_none());
+    }
+
+    #[test]
+    fn unresolvable_instruction_keeps_the_transaction_unnormalized() {
+        let (normalized, json_parsed) = normalize_encoded_transaction(parsed_transaction(vec![
+            partially_decoded(SYSTEM_PROGRAM, &["missing"]),
+            system_transfer(),
+        ]));
+        assert!(matches!(
+            normalized.transaction,
+            EncodedTransaction::Json(UiTransaction {
+                message: UiMessage::Parsed(_),
+                ..
+            })
+        ));
+        assert!(json_parsed.is_empty());
+    }
+
+    #[test]
+    fn unknown_accounts_are_not_resolved_to_fee_payer() {
+        let keys = ["payer", "account"].map(String::from);
+        assert!(to_compiled_instruction(&partially_decoded("program", &["account"]), &keys).is_err());
+        assert!(to_compiled_instruction(&partially_decoded("account", &["missing"]), &keys).is_err());
+    }
+}
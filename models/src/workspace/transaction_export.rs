@@ -0,0 +1,195 @@
+use crate::workspace::cheap_block_transaction::ParsedVersionedTransactionWithStatusMeta;
+use crate::workspace::transaction_filter::program_id_filter::{
+    ProgramInvocationScope, invoked_program_ids,
+};
+use crate::workspace::transaction_service::FilteredHandlingData;
+use crate::workspace::transaction_sort::{TransactionSort, TransactionSortScope};
+use dioxus::logger::tracing::warn;
+
+const CSV_HEADER: &str = "slot,index,signature,signer,status,fee,compute_units,programs";
+
+/// 按列表当前的显示顺序返回过滤后的交易及其在区块中的下标，单笔交易没有下标
+fn exported_transactions<'a>(
+    data: &'a FilteredHandlingData,
+    sort: &TransactionSort,
+) -> Vec<(Option<usize>, &'a ParsedVersionedTransactionWithStatusMeta)> {
+    match data {
+        FilteredHandlingData::Query(tx) => vec![(None, tx)],
+        FilteredHandlingData::QueryNearby(blocks) => match sort.scope {
+            TransactionSortScope::WithinBlock => blocks
+                .iter()
+                .flat_map(|b| b.iter().map(|(i, tx)| (Some(i), tx)))
+                .collect(),
+            TransactionSortScope::AcrossBlocks => sort
+                .flatten(blocks)
+                .into_iter()
+                .map(|p| {
+                    let tx = &blocks[p.block].block.transactions[p.index];
+                    (Some(p.index), tx)
+                })
+                .collect(),
+        },
+    }
+}
+
+/// 导出为CSV，每笔交易一行，调用的程序(包括CPI)以分号分隔
+pub fn export_csv(data: &FilteredHandlingData, sort: &TransactionSort) -> String {
+    let mut csv = String::from(CSV_HEADER);
+    csv.push('\n');
+    for (index, tx) in exported_transactions(data, sort) {
+        let status = match tx.is_success() {
+            Some(true) => "success",
+            Some(false) => "failed",
+            None => "",
+        };
+        let programs = invoked_program_ids(tx, ProgramInvocationScope::IncludingCpi).join(";");
+        let row = [
+            tx.slot().to_string(),
+            index.map(|i| i.to_string()).unwrap_or_default(),
+            tx.signature().map(|s| s.to_string()).unwrap_or_default(),
+            tx.fee_payer().map(|s| s.to_string()).unwrap_or_default(),
+            status.to_string(),
+            tx.fee().map(|f| f.to_string()).unwrap_or_default(),
+            tx.compute_units_consumed()
+                .map(|c| c.to_string())
+                .unwrap_or_default(),
+            programs,
+        ];
+        let row = row.iter().map(|f| escape_csv(f)).collect::<Vec<_>>();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// 导出为Json格式的完整交易数组，可再次导入
+pub fn export_json(data: &FilteredHandlingData, sort: &TransactionSort) -> serde_json::Result<String> {
+    let transactions = exported_transactions(data, sort)
+        .into_iter()
+        .filter_map(|(_, tx)| {
+            let encoded = tx.to_encoded();
+            if encoded.is_none() {
+                warn!("交易未能解码，导出时被忽略, slot: {}", tx.slot());
+            }
+            encoded
+        })
+        .collect::<Vec<_>>();
+    serde_json::to_string_pretty(&transactions)
+}
+
+fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::transaction_import::{ImportedData, parse_import};
+    use serde_json::json;
+
+    const SYSTEM_PROGRAM: &str = "11111111111111111111111111111111";
+
+    /// jsonParsed编码的v0交易，包含已解析、部分解码的指令，内部指令，以及通过地址表加载的帐号
+    fn json_parsed_transaction() -> ParsedVersionedTransactionWithStatusMeta {
+        let account = |pubkey: &str, signer: bool, writable: bool, source: &str| {
+            json!({"pubkey": pubkey, "signer": signer, "writable": writable, "source": source})
+        };
+        let transaction = serde_json::from_value(json!({
+            "slot": 100,
+            "blockTime": 1_700_000_000,
+            "version": 0,
+            "transaction": {
+                "signatures": [
+                    "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW"
+                ],
+                "message": {
+                    "accountKeys": [
+                        account("7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU", true, true, "transaction"),
+                        account("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM", false, true, "transaction"),
+                        account(SYSTEM_PROGRAM, false, false, "transaction"),
+                        account("ComputeBudget111111111111111111111111111111", false, false, "transaction"),
+                        account("So11111111111111111111111111111111111111112", false, false, "lookupTable")
+                    ],
+                    "recentBlockhash": "EkSnNWid2cvwEVnVx9aBqawnmiCNiDgp3gUdkDPTKN1N",
+                    "instructions": [
+                        {
+                            "programId": "ComputeBudget111111111111111111111111111111",
+                            "accounts": [],
+                            "data": "3DdGGhkhJbjm",
+                            "stackHeight": null
+                        },
+                        {
+                            "program": "system",
+                            "programId": SYSTEM_PROGRAM,
+                            "parsed": {"type": "transfer", "info": {"lamports": 5000}},
+                            "stackHeight": null
+                        }
+                    ],
+                    "addressTableLookups": [
+                        {
+                            "accountKey": "AddressLookupTab1e1111111111111111111111111",
+                            "writableIndexes": [],
+                            "readonlyIndexes": [0]
+                        }
+                    ]
+                }
+            },
+            "meta": {
+                "err": null,
+                "status": {"Ok": null},
+                "fee": 5000,
+                "preBalances": [1_000_000_000u64, 0, 1, 1, 1],
+                "postBalances": [999_990_000u64, 5000, 1, 1, 1],
+                "innerInstructions": [
+                    {
+                        "index": 1,
+                        "instructions": [
+                            {
+                                "program": "system",
+                                "programId": SYSTEM_PROGRAM,
+                                "parsed": {"type": "allocate", "info": {"space": 0}},
+                                "stackHeight": 2
+                            },
+                            {
+                                "programId": SYSTEM_PROGRAM,
+                                "accounts": ["7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU"],
+                                "data": "3Bxs4h24hBtQy9rw",
+                                "stackHeight": 2
+                            }
+                        ]
+                    }
+                ],
+                "logMessages": [],
+                "preTokenBalances": [],
+                "postTokenBalances": [],
+                "rewards": []
+            }
+        }))
+        .unwrap();
+        ParsedVersionedTransactionWithStatusMeta::new(transaction)
+    }
+
+    #[test]
+    fn json_parsed_instructions_survive_export_and_import() {
+        let tx = json_parsed_transaction();
+        assert!(tx.transaction.transaction.is_some());
+        assert!(tx.json_parsed_instructions.get(1, None).is_some());
+        assert!(tx.json_parsed_instructions.get(1, Some(0)).is_some());
+
+        let data = FilteredHandlingData::Query(tx.clone());
+        let json = export_json(&data, &TransactionSort::default()).unwrap();
+        let ImportedData::Blocks(blocks) = parse_import(&json, None).unwrap() else {
+            panic!("导出的是交易数组，应导入为区块");
+        };
+        let imported = &blocks[0].transactions[0];
+        assert_eq!(imported.transaction, tx.transaction);
+        assert_eq!(
+            imported.json_parsed_instructions,
+            tx.json_parsed_instructions
+        );
+    }
+}
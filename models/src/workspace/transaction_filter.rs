@@ -0,0 +1,4 @@
+pub mod program_id_filter;
+pub mod range_filter;
+pub mod log_message_filter;
+pub mod token_balance_filter;
\ No newline at end of file
@@ -0,0 +1,134 @@
+use crate::workspace::log_tree::{is_program_output, parse_invoke, parse_invoke_end};
+use block_insight_cross::transaction::transaction_filter::{
+    TransactionFilter, TransactionFilterContext, TransactionPropsProvider,
+};
+use regex::Regex;
+
+/// 日志的匹配方式
+#[derive(Clone, Debug)]
+pub enum LogPattern {
+    Substring(String),
+    Regex(Regex),
+}
+
+impl LogPattern {
+    fn is_match(&self, log: &str) -> bool {
+        match self {
+            LogPattern::Substring(s) => log.contains(s.as_str()),
+            LogPattern::Regex(r) => r.is_match(log),
+        }
+    }
+}
+
+/// 保留有日志与指定模式匹配的交易
+/// 如果指定了program_id，则只匹配该程序调用期间(invoke到success/failed之间，不含其CPI调用)
+/// 由程序输出的"Program log: "及"Program data: "日志，不匹配invoke/consumed/success等运行时日志
+#[derive(Clone, Debug)]
+pub struct LogMessageFilter {
+    pattern: LogPattern,
+    program_id: Option<String>,
+}
+
+impl LogMessageFilter {
+    pub fn substring(content: String, program_id: Option<String>) -> Self {
+        LogMessageFilter {
+            pattern: LogPattern::Substring(content),
+            program_id,
+        }
+    }
+
+    pub fn regex(content: &str, program_id: Option<String>) -> Result<Self, regex::Error> {
+        Ok(LogMessageFilter {
+            pattern: LogPattern::Regex(Regex::new(content)?),
+            program_id,
+        })
+    }
+
+    fn is_match(&self, logs: &[String]) -> bool {
+        match &self.program_id {
+            None => logs.iter().any(|log| self.pattern.is_match(log)),
+            Some(program_id) => {
+                let mut invoking: Vec<&str> = Vec::new();
+                for log in logs {
+                    if is_program_output(log) {
+                        let emitted_by_program = invoking.last().is_some_and(|id| id == program_id);
+                        if emitted_by_program && self.pattern.is_match(log) {
+                            return true;
+                        }
+                    } else if let Some((id, _)) = parse_invoke(log) {
+                        invoking.push(id);
+                    } else if parse_invoke_end(log).is_some() {
+                        invoking.pop();
+                    }
+                }
+                false
+            }
+        }
+    }
+}
+
+impl TransactionFilter for LogMessageFilter {
+    type ContextType = TransactionFilterContext;
+
+    fn filter(
+        &self,
+        transaction: &dyn TransactionPropsProvider,
+        _context: &mut Self::ContextType,
+    ) -> bool {
+        transaction
+            .get_meta()
+            .and_then(|meta| Option::<&[String]>::from(meta.log_messages))
+            .is_some_and(|logs| self.is_match(logs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+    const OTHER: &str = "ComputeBudget111111111111111111111111111111";
+
+    fn logs() -> Vec<String> {
+        vec![
+            format!("Program {PROGRAM} invoke [1]"),
+            "Program log: failed to find account".to_string(),
+            "Program log: transfer".to_string(),
+            format!("Program {OTHER} invoke [2]"),
+            "Program log: swap".to_string(),
+            format!("Program {OTHER} success"),
+            "Program log: done".to_string(),
+            format!("Program {PROGRAM} success"),
+        ]
+    }
+
+    fn scoped(content: &str) -> LogMessageFilter {
+        LogMessageFilter::substring(content.to_string(), Some(PROGRAM.to_string()))
+    }
+
+    #[test]
+    fn matches_any_line_without_program() {
+        let filter = LogMessageFilter::substring("swap".to_string(), None);
+        assert!(filter.is_match(&logs()));
+        let filter = LogMessageFilter::regex("^Program log: t.*r$", None).unwrap();
+        assert!(filter.is_match(&logs()));
+    }
+
+    #[test]
+    fn program_scope_excludes_cpi_output() {
+        assert!(scoped("transfer").is_match(&logs()));
+        assert!(!scoped("swap").is_match(&logs()));
+    }
+
+    #[test]
+    fn program_output_does_not_end_scope() {
+        // "failed"开头的程序日志不应结束调用，之后的日志仍属于该程序
+        assert!(scoped("done").is_match(&logs()));
+    }
+
+    #[test]
+    fn program_scope_ignores_runtime_lines() {
+        assert!(!scoped("invoke").is_match(&logs()));
+        assert!(!scoped("success").is_match(&logs()));
+    }
+}
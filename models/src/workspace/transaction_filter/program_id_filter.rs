@@ -0,0 +1,99 @@
+use block_insight_cross::parsed_instruction::ParsedInstruction;
+use block_insight_cross::transaction::transaction_filter::{
+    TransactionFilter, TransactionFilterContext, TransactionPropsProvider,
+};
+use block_insight_cross::utils::TransactionAccounts;
+
+/// 程序调用的匹配范围
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ProgramInvocationScope {
+    /// 只匹配顶层指令
+    TopLevel,
+    /// 同时匹配通过CPI调用的内部指令
+    IncludingCpi,
+}
+
+/// 保留调用了指定程序的交易，程序ID通过交易的帐号列表及加载的地址解析
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProgramIdFilter {
+    program_ids: Vec<String>,
+    scope: ProgramInvocationScope,
+}
+
+impl ProgramIdFilter {
+    pub fn new(program_ids: Vec<String>, scope: ProgramInvocationScope) -> Self {
+        ProgramIdFilter { program_ids, scope }
+    }
+
+    fn invokes(
+        &self,
+        instruction: &ParsedInstruction,
+        accounts: &TransactionAccounts<'_, String>,
+    ) -> bool {
+        let program_id = accounts.get(instruction.program_id_index as usize);
+        if program_id.is_some_and(|id| self.program_ids.contains(id)) {
+            return true;
+        }
+
+        if self.scope == ProgramInvocationScope::TopLevel {
+            return false;
+        }
+
+        instruction
+            .inner_instructions
+            .as_ref()
+            .is_some_and(|inner| inner.iter().any(|i| self.invokes(i, accounts)))
+    }
+}
+
+/// 交易调用的所有程序ID，按首次出现的顺序去重
+pub fn invoked_program_ids(
+    transaction: &dyn TransactionPropsProvider,
+    scope: ProgramInvocationScope,
+) -> Vec<String> {
+    fn collect(
+        instruction: &ParsedInstruction,
+        accounts: &TransactionAccounts<'_, String>,
+        scope: ProgramInvocationScope,
+        ids: &mut Vec<String>,
+    ) {
+        if let Some(id) = accounts.get(instruction.program_id_index as usize)
+            && !ids.contains(id)
+        {
+            ids.push(id.clone());
+        }
+        if scope == ProgramInvocationScope::TopLevel {
+            return;
+        }
+        if let Some(inner) = instruction.inner_instructions.as_ref() {
+            for i in inner.iter() {
+                collect(i, accounts, scope, ids);
+            }
+        }
+    }
+
+    let mut ids = Vec::new();
+    if let Some(instructions) = transaction.get_parsed_instructions() {
+        let accounts = transaction.get_accounts();
+        for i in instructions {
+            collect(i, &accounts, scope, &mut ids);
+        }
+    }
+    ids
+}
+
+impl TransactionFilter for ProgramIdFilter {
+    type ContextType = TransactionFilterContext;
+
+    fn filter(
+        &self,
+        transaction: &dyn TransactionPropsProvider,
+        _context: &mut Self::ContextType,
+    ) -> bool {
+        let Some(instructions) = transaction.get_parsed_instructions() else {
+            return false;
+        };
+        let accounts = transaction.get_accounts();
+        instructions.iter().any(|i| self.invokes(i, &accounts))
+    }
+}
@@ -0,0 +1,88 @@
+use block_insight_cross::transaction::transaction_filter::{
+    TransactionFilter, TransactionFilterContext, TransactionPropsProvider,
+};
+
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+/// 闭区间，未设置的一端不做限制
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct U64Range {
+    pub min: Option<u64>,
+    pub max: Option<u64>,
+}
+
+impl U64Range {
+    pub fn new(min: Option<u64>, max: Option<u64>) -> Self {
+        U64Range { min, max }
+    }
+
+    pub fn is_unbounded(&self) -> bool {
+        self.min.is_none() && self.max.is_none()
+    }
+
+    pub fn contains(&self, value: u64) -> bool {
+        self.min.is_none_or(|min| value >= min) && self.max.is_none_or(|max| value <= max)
+    }
+}
+
+/// 按交易手续费(lamports)过滤
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FeeRangeFilter {
+    pub range: U64Range,
+}
+
+impl FeeRangeFilter {
+    pub fn new(range: U64Range) -> Self {
+        FeeRangeFilter { range }
+    }
+}
+
+impl TransactionFilter for FeeRangeFilter {
+    type ContextType = TransactionFilterContext;
+
+    fn filter(
+        &self,
+        transaction: &dyn TransactionPropsProvider,
+        _context: &mut Self::ContextType,
+    ) -> bool {
+        transaction
+            .get_meta()
+            .is_some_and(|meta| self.range.contains(meta.fee))
+    }
+}
+
+/// 按交易实际消耗的计算单元过滤，没有计算单元数据的交易不满足条件
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ComputeUnitRangeFilter {
+    pub range: U64Range,
+}
+
+impl ComputeUnitRangeFilter {
+    pub fn new(range: U64Range) -> Self {
+        ComputeUnitRangeFilter { range }
+    }
+}
+
+impl TransactionFilter for ComputeUnitRangeFilter {
+    type ContextType = TransactionFilterContext;
+
+    fn filter(
+        &self,
+        transaction: &dyn TransactionPropsProvider,
+        _context: &mut Self::ContextType,
+    ) -> bool {
+        transaction
+            .get_meta()
+            .and_then(|meta| Option::<u64>::from(meta.compute_units_consumed))
+            .is_some_and(|cu| self.range.contains(cu))
+    }
+}
+
+/// 将以SOL为单位的字符串转换为lamports
+pub fn parse_sol_to_lamports(content: &str) -> anyhow::Result<u64> {
+    let sol = content.trim().parse::<f64>()?;
+    if !sol.is_finite() || sol < 0.0 {
+        anyhow::bail!("无效的SOL数量: {content}");
+    }
+    Ok((sol * LAMPORTS_PER_SOL as f64).round() as u64)
+}
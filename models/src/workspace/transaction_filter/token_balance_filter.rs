@@ -0,0 +1,169 @@
+use crate::workspace::balance_change::{TokenBalanceChange, token_balance_changes};
+use block_insight_cross::transaction::transaction_filter::{
+    TransactionFilter, TransactionFilterContext, TransactionPropsProvider,
+};
+use serde::{Deserialize, Serialize};
+
+/// 代币余额的变化方向
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenBalanceDirection {
+    /// 余额发生变化
+    Any,
+    Increase,
+    Decrease,
+}
+
+/// 保留指定代币余额发生变化的交易，mint及所有者为空时不做限制，
+/// min_change为按精度换算后的最小变化量(绝对值)
+#[derive(Clone, Debug, PartialEq)]
+pub struct TokenBalanceFilter {
+    pub mint: Option<String>,
+    pub owner: Option<String>,
+    pub direction: TokenBalanceDirection,
+    pub min_change: Option<f64>,
+}
+
+impl TokenBalanceFilter {
+    fn matches_change(&self, change: &TokenBalanceChange) -> bool {
+        if self.mint.as_ref().is_some_and(|m| *m != change.mint) {
+            return false;
+        }
+        if self
+            .owner
+            .as_ref()
+            .is_some_and(|o| change.owner.as_ref() != Some(o))
+        {
+            return false;
+        }
+        let delta = change.delta();
+        let direction_matched = match self.direction {
+            TokenBalanceDirection::Any => delta != 0,
+            TokenBalanceDirection::Increase => delta > 0,
+            TokenBalanceDirection::Decrease => delta < 0,
+        };
+        direction_matched
+            && self
+                .min_change
+                .is_none_or(|min| change.ui_delta().abs() >= min)
+    }
+}
+
+impl TransactionFilter for TokenBalanceFilter {
+    type ContextType = TransactionFilterContext;
+
+    fn filter(
+        &self,
+        transaction: &dyn TransactionPropsProvider,
+        _context: &mut Self::ContextType,
+    ) -> bool {
+        token_balance_changes(
+            transaction.get_pre_token_balances().unwrap_or_default(),
+            transaction.get_post_token_balances().unwrap_or_default(),
+        )
+        .iter()
+        .any(|c| self.matches_change(c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::cheap_block_transaction::ParsedVersionedTransactionWithStatusMeta;
+    use serde_json::{Value, json};
+
+    const OWNER: &str = "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU";
+    const USDC: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+    fn token_balance(amount: u64) -> Value {
+        json!({
+            "accountIndex": 1,
+            "mint": USDC,
+            "owner": OWNER,
+            "programId": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+            "uiTokenAmount": {
+                "uiAmount": amount as f64 / 1e6,
+                "decimals": 6,
+                "amount": amount.to_string(),
+                "uiAmountString": (amount as f64 / 1e6).to_string()
+            }
+        })
+    }
+
+    /// 所有者的USDC余额从3减少到1
+    fn transaction() -> ParsedVersionedTransactionWithStatusMeta {
+        let transaction = serde_json::from_value(json!({
+            "transaction": {
+                "signatures": [
+                    "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW"
+                ],
+                "message": {
+                    "header": {
+                        "numRequiredSignatures": 1,
+                        "numReadonlySignedAccounts": 0,
+                        "numReadonlyUnsignedAccounts": 1
+                    },
+                    "accountKeys": [
+                        OWNER,
+                        "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM",
+                        "ComputeBudget111111111111111111111111111111"
+                    ],
+                    "recentBlockhash": "EkSnNWid2cvwEVnVx9aBqawnmiCNiDgp3gUdkDPTKN1N",
+                    "instructions": [
+                        {"programIdIndex": 2, "accounts": [], "data": "3DdGGhkhJbjm", "stackHeight": null}
+                    ]
+                }
+            },
+            "meta": {
+                "err": null,
+                "status": {"Ok": null},
+                "fee": 5000,
+                "preBalances": [1_000_000_000u64, 2_039_280, 1],
+                "postBalances": [999_995_000u64, 2_039_280, 1],
+                "logMessages": [],
+                "preTokenBalances": [token_balance(3_000_000)],
+                "postTokenBalances": [token_balance(1_000_000)],
+                "rewards": []
+            },
+            "version": "legacy"
+        }))
+        .unwrap();
+        ParsedVersionedTransactionWithStatusMeta::from_encoded_transaction_with_status_meta(
+            transaction,
+            100,
+            None,
+        )
+    }
+
+    fn passes(filter: TokenBalanceFilter) -> bool {
+        filter.filter(&transaction(), &mut TransactionFilterContext::default())
+    }
+
+    fn filter(direction: TokenBalanceDirection) -> TokenBalanceFilter {
+        TokenBalanceFilter {
+            mint: Some(USDC.to_string()),
+            owner: Some(OWNER.to_string()),
+            direction,
+            min_change: None,
+        }
+    }
+
+    #[test]
+    fn reads_token_balances_from_props_provider() {
+        assert!(passes(filter(TokenBalanceDirection::Any)));
+        assert!(passes(filter(TokenBalanceDirection::Decrease)));
+        assert!(!passes(filter(TokenBalanceDirection::Increase)));
+        assert!(passes(TokenBalanceFilter {
+            min_change: Some(2.0),
+            ..filter(TokenBalanceDirection::Decrease)
+        }));
+        assert!(!passes(TokenBalanceFilter {
+            min_change: Some(2.5),
+            ..filter(TokenBalanceDirection::Decrease)
+        }));
+        assert!(!passes(TokenBalanceFilter {
+            mint: Some("So11111111111111111111111111111111111111112".to_string()),
+            ..filter(TokenBalanceDirection::Any)
+        }));
+    }
+}
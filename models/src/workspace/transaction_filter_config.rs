@@ -0,0 +1,209 @@
+use crate::workspace::transaction_filter::log_message_filter::LogMessageFilter;
+use crate::workspace::transaction_filter::program_id_filter::{
+    ProgramIdFilter, ProgramInvocationScope,
+};
+use crate::workspace::transaction_filter::range_filter::{
+    ComputeUnitRangeFilter, FeeRangeFilter, U64Range,
+};
+use crate::workspace::transaction_filter::token_balance_filter::{
+    TokenBalanceDirection, TokenBalanceFilter,
+};
+use crate::workspace::transaction_filter_expression::{
+    BoxedTransactionFilter, FilterGroupOperator, TransactionFilterExpression, TransactionFilterId,
+};
+use block_insight_cross::transaction::transaction_filter::account_filter::AccountFilter;
+use block_insight_cross::transaction::transaction_filter::circle_swap_filter::CircleSwapFilter;
+use block_insight_cross::transaction::transaction_filter::signature_filter::SignatureFilter;
+use block_insight_cross::transaction::transaction_filter::status_filter::TransactionStatusFilter;
+use serde::{Deserialize, Serialize};
+
+/// 单个筛选条件的配置，可序列化，用于保存筛选面板的状态及生成分享链接
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FilterItemSpec {
+    Signature {
+        signatures: Vec<String>,
+    },
+    Account {
+        accounts: Vec<String>,
+    },
+    Status {
+        success: bool,
+    },
+    CircleSwap,
+    ProgramId {
+        program_ids: Vec<String>,
+        including_cpi: bool,
+    },
+    /// 手续费范围(lamports)
+    Fee {
+        min: Option<u64>,
+        max: Option<u64>,
+    },
+    ComputeUnits {
+        min: Option<u64>,
+        max: Option<u64>,
+    },
+    LogMessage {
+        content: String,
+        use_regex: bool,
+        program_id: Option<String>,
+    },
+    /// 代币余额变化，min_change为按精度换算后的数量
+    TokenBalance {
+        mint: Option<String>,
+        owner: Option<String>,
+        direction: TokenBalanceDirection,
+        min_change: Option<f64>,
+    },
+}
+
+impl FilterItemSpec {
+    /// 构建对应的过滤器
+    pub fn to_filter(&self) -> anyhow::Result<BoxedTransactionFilter> {
+        let filter: BoxedTransactionFilter = match self {
+            FilterItemSpec::TokenBalance {
+                mint,
+                owner,
+                direction,
+                min_change,
+            } => Box::new(TokenBalanceFilter {
+                mint: mint.clone(),
+                owner: owner.clone(),
+                direction: *direction,
+                min_change: *min_change,
+            }),
+            FilterItemSpec::Signature { signatures } => {
+                Box::new(SignatureFilter::Include(signatures.clone()))
+            }
+            FilterItemSpec::Account { accounts } => {
+                Box::new(AccountFilter::Include(accounts.clone()))
+            }
+            FilterItemSpec::Status { success } => Box::new(if *success {
+                TransactionStatusFilter::success()
+            } else {
+                TransactionStatusFilter::fail()
+            }),
+            FilterItemSpec::CircleSwap => Box::new(CircleSwapFilter),
+            FilterItemSpec::ProgramId {
+                program_ids,
+                including_cpi,
+            } => {
+                let scope = if *including_cpi {
+                    ProgramInvocationScope::IncludingCpi
+                } else {
+                    ProgramInvocationScope::TopLevel
+                };
+                Box::new(ProgramIdFilter::new(program_ids.clone(), scope))
+            }
+            FilterItemSpec::Fee { min, max } => {
+                Box::new(FeeRangeFilter::new(U64Range::new(*min, *max)))
+            }
+            FilterItemSpec::ComputeUnits { min, max } => {
+                Box::new(ComputeUnitRangeFilter::new(U64Range::new(*min, *max)))
+            }
+            FilterItemSpec::LogMessage {
+                content,
+                use_regex,
+                program_id,
+            } => Box::new(if *use_regex {
+                LogMessageFilter::regex(content, program_id.clone())?
+            } else {
+                LogMessageFilter::substring(content.clone(), program_id.clone())
+            }),
+        };
+        Ok(filter)
+    }
+}
+
+/// 筛选组中的单个筛选条件，id不参与序列化，反序列化时重新生成
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FilterItemConfig {
+    #[serde(skip, default = "TransactionFilterId::next")]
+    pub id: TransactionFilterId,
+    /// 为true时排除满足此条件的交易
+    #[serde(default)]
+    pub exclude: bool,
+    #[serde(flatten)]
+    pub spec: FilterItemSpec,
+}
+
+impl FilterItemConfig {
+    pub fn new(id: TransactionFilterId, exclude: bool, spec: FilterItemSpec) -> Self {
+        FilterItemConfig { id, exclude, spec }
+    }
+
+    pub fn to_expression(&self) -> anyhow::Result<TransactionFilterExpression> {
+        let expression = TransactionFilterExpression::filter_with_id(self.id, self.spec.to_filter()?);
+        if self.exclude {
+            Ok(!expression)
+        } else {
+            Ok(expression)
+        }
+    }
+}
+
+/// 无法构建过滤器的筛选项及原因
+#[derive(Clone, Debug, PartialEq)]
+pub struct FilterBuildError {
+    pub id: TransactionFilterId,
+    pub message: String,
+}
+
+/// 筛选面板中一个筛选组的完整配置，与[TransactionFilterExpression]不同，它可以被序列化
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FilterGroupConfig {
+    pub operator: FilterGroupOperator,
+    #[serde(default)]
+    pub negated: bool,
+    #[serde(default)]
+    pub items: Vec<FilterItemConfig>,
+    #[serde(default)]
+    pub children: Vec<FilterGroupConfig>,
+}
+
+impl Default for FilterGroupConfig {
+    fn default() -> Self {
+        FilterGroupConfig {
+            operator: FilterGroupOperator::And,
+            negated: false,
+            items: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+}
+
+impl FilterGroupConfig {
+    /// 递归构建过滤表达式，无法构建的筛选条件会被忽略，并将原因记录到errors中
+    pub fn to_expression(
+        &self,
+        errors: &mut Vec<FilterBuildError>,
+    ) -> TransactionFilterExpression {
+        let mut children = self
+            .items
+            .iter()
+            .filter_map(|item| match item.to_expression() {
+                Ok(expression) => Some(expression),
+                Err(e) => {
+                    errors.push(FilterBuildError {
+                        id: item.id,
+                        message: format!("{e:#}"),
+                    });
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        children.extend(self.children.iter().map(|c| c.to_expression(errors)));
+        let expression = TransactionFilterExpression::group(self.operator, children);
+        if self.negated {
+            !expression
+        } else {
+            expression
+        }
+    }
+
+    /// 是否不含任何筛选条件
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty() && self.children.iter().all(|c| c.is_empty())
+    }
+}
@@ -0,0 +1,533 @@
+use crate::workspace::cheap_block_transaction::{
+    CheapBlockTransaction, FilteredBlockTransaction, ParsedVersionedTransactionWithStatusMeta,
+};
+use block_insight_cross::transaction::transaction_filter::{
+    TransactionFilter, TransactionFilterContext,
+};
+use serde::{Deserialize, Serialize};
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+pub type BoxedTransactionFilter = Box<dyn TransactionFilter<ContextType = TransactionFilterContext>>;
+
+static NEXT_TRANSACTION_FILTER_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// 过滤器在表达式树中的唯一标识，同类型的多个过滤器可以通过它区分
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TransactionFilterId(usize);
+
+impl TransactionFilterId {
+    /// 生成一个新的唯一标识
+    pub fn next() -> Self {
+        TransactionFilterId(NEXT_TRANSACTION_FILTER_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl Display for TransactionFilterId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// 单个过滤器在一次过滤中的统计数据，被短路跳过的交易不计入
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct FilterStatistic {
+    /// 被该过滤器检查过的交易数
+    pub evaluated: usize,
+    /// 满足该过滤器的交易数
+    pub passed: usize,
+    /// 不满足该过滤器的交易数
+    pub rejected: usize,
+}
+
+/// 一次过滤的统计数据
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TransactionFilterStatistics {
+    /// 每个过滤器的统计
+    pub filters: HashMap<TransactionFilterId, FilterStatistic>,
+    /// 参与过滤的交易总数
+    pub total: usize,
+    /// 过滤后保留的交易数
+    pub kept: usize,
+}
+
+impl TransactionFilterStatistics {
+    pub fn get(&self, id: &TransactionFilterId) -> Option<&FilterStatistic> {
+        self.filters.get(id)
+    }
+
+    /// 合并另一次过滤的统计数据，用于只对新增数据进行过滤的场景
+    pub fn merge(&mut self, other: TransactionFilterStatistics) {
+        for (id, statistic) in other.filters {
+            let current = self.filters.entry(id).or_default();
+            current.evaluated += statistic.evaluated;
+            current.passed += statistic.passed;
+            current.rejected += statistic.rejected;
+        }
+        self.total += other.total;
+        self.kept += other.kept;
+    }
+
+    fn record(&mut self, id: TransactionFilterId, passed: bool) {
+        let statistic = self.filters.entry(id).or_default();
+        statistic.evaluated += 1;
+        if passed {
+            statistic.passed += 1;
+        } else {
+            statistic.rejected += 1;
+        }
+    }
+}
+
+/// 过滤组内各子表达式的组合方式
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterGroupOperator {
+    And,
+    Or,
+}
+
+/// 交易过滤表达式树，可任意嵌套且/或/非
+/// 空的组(不包含任何过滤器的子树)不构成任何约束，在求值时会被忽略
+pub enum TransactionFilterExpression {
+    /// 单个过滤器
+    Filter {
+        id: TransactionFilterId,
+        filter: BoxedTransactionFilter,
+    },
+    /// 所有子表达式都满足时满足
+    And(Vec<TransactionFilterExpression>),
+    /// 任一子表达式满足时满足
+    Or(Vec<TransactionFilterExpression>),
+    /// 子表达式不满足时满足
+    Not(Box<TransactionFilterExpression>),
+}
+
+impl Default for TransactionFilterExpression {
+    fn default() -> Self {
+        TransactionFilterExpression::And(Vec::new())
+    }
+}
+
+impl std::ops::Not for TransactionFilterExpression {
+    type Output = TransactionFilterExpression;
+
+    fn not(self) -> Self::Output {
+        TransactionFilterExpression::Not(Box::new(self))
+    }
+}
+
+impl TransactionFilterExpression {
+    /// 以新的标识构建单个过滤器节点
+    pub fn filter(
+        filter: impl TransactionFilter<ContextType = TransactionFilterContext> + 'static,
+    ) -> Self {
+        Self::filter_with_id(TransactionFilterId::next(), Box::new(filter))
+    }
+
+    pub fn filter_with_id(id: TransactionFilterId, filter: BoxedTransactionFilter) -> Self {
+        TransactionFilterExpression::Filter { id, filter }
+    }
+
+    pub fn group(operator: FilterGroupOperator, children: Vec<TransactionFilterExpression>) -> Self {
+        match operator {
+            FilterGroupOperator::And => TransactionFilterExpression::And(children),
+            FilterGroupOperator::Or => TransactionFilterExpression::Or(children),
+        }
+    }
+
+    /// 表达式中是否不含任何过滤器
+    pub fn is_empty(&self) -> bool {
+        match self {
+            TransactionFilterExpression::Filter { .. } => false,
+            TransactionFilterExpression::And(children)
+            | TransactionFilterExpression::Or(children) => children.iter().all(|c| c.is_empty()),
+            TransactionFilterExpression::Not(inner) => inner.is_empty(),
+        }
+    }
+
+    /// 以且的关系追加一个过滤器，如果根节点不是且组，则将原根节点与新过滤器组成新的且组
+    pub fn and_filter(&mut self, filter: BoxedTransactionFilter) -> TransactionFilterId {
+        let id = TransactionFilterId::next();
+        let filter = Self::filter_with_id(id, filter);
+        match self {
+            TransactionFilterExpression::And(children) => children.push(filter),
+            _ => {
+                let old = std::mem::take(self);
+                *self = TransactionFilterExpression::And(vec![old, filter]);
+            }
+        }
+        id
+    }
+
+    /// 移除树中所有指定类型的过滤器
+    pub fn remove_filters_by_type(&mut self, type_id: TypeId) {
+        self.remove_filters_where(&mut |_, filter_type| filter_type == type_id);
+    }
+
+    /// 移除指定标识的过滤器，返回是否有过滤器被移除
+    pub fn remove_filter_by_id(&mut self, id: TransactionFilterId) -> bool {
+        let mut removed = false;
+        self.remove_filters_where(&mut |filter_id, _| {
+            if filter_id == id {
+                removed = true;
+                true
+            } else {
+                false
+            }
+        });
+        removed
+    }
+
+    /// 树中所有过滤器的标识，按深度优先顺序
+    pub fn filter_ids(&self) -> Vec<TransactionFilterId> {
+        let mut ids = Vec::new();
+        self.collect_filter_ids(&mut ids);
+        ids
+    }
+
+    fn collect_filter_ids(&self, ids: &mut Vec<TransactionFilterId>) {
+        match self {
+            TransactionFilterExpression::Filter { id, .. } => ids.push(*id),
+            TransactionFilterExpression::And(children)
+            | TransactionFilterExpression::Or(children) => {
+                for child in children {
+                    child.collect_filter_ids(ids);
+                }
+            }
+            TransactionFilterExpression::Not(inner) => inner.collect_filter_ids(ids),
+        }
+    }
+
+    /// 移除所有满足条件的过滤器节点，被移除的节点替换为空组，从而不再构成约束，
+    /// 移除后变为空的子树会从所在的组中剪除，predicate的参数为过滤器的标识及类型
+    fn remove_filters_where(
+        &mut self,
+        predicate: &mut impl FnMut(TransactionFilterId, TypeId) -> bool,
+    ) {
+        match self {
+            TransactionFilterExpression::Filter { id, filter } => {
+                if predicate(*id, filter.id()) {
+                    *self = TransactionFilterExpression::default();
+                }
+            }
+            TransactionFilterExpression::And(children)
+            | TransactionFilterExpression::Or(children) => {
+                for child in children.iter_mut() {
+                    child.remove_filters_where(predicate);
+                }
+                children.retain(|c| !c.is_empty());
+            }
+            TransactionFilterExpression::Not(inner) => inner.remove_filters_where(predicate),
+        }
+    }
+
+    /// 交易是否满足表达式，空表达式总是满足
+    pub fn matches(
+        &self,
+        transaction: &ParsedVersionedTransactionWithStatusMeta,
+        context: &mut TransactionFilterContext,
+    ) -> bool {
+        self.evaluate(transaction, context, None).unwrap_or(true)
+    }
+
+    /// 与[Self::matches]相同，同时将每个过滤器的结果记录到statistics中
+    pub fn matches_with_statistics(
+        &self,
+        transaction: &ParsedVersionedTransactionWithStatusMeta,
+        context: &mut TransactionFilterContext,
+        statistics: &mut TransactionFilterStatistics,
+    ) -> bool {
+        let keep = self
+            .evaluate(transaction, context, Some(statistics))
+            .unwrap_or(true);
+        statistics.total += 1;
+        if keep {
+            statistics.kept += 1;
+        }
+        keep
+    }
+
+    /// 对所有区块进行过滤，结果只记录通过过滤的交易下标，不复制交易数据
+    pub fn filter_blocks(
+        &self,
+        data: &[CheapBlockTransaction],
+        context: &mut TransactionFilterContext,
+        statistics: &mut TransactionFilterStatistics,
+    ) -> Vec<FilteredBlockTransaction> {
+        if self.is_empty() {
+            let total = data.iter().map(|d| d.transactions.len()).sum();
+            statistics.total = total;
+            statistics.kept = total;
+            return data.iter().map(FilteredBlockTransaction::unfiltered).collect();
+        }
+
+        data.iter()
+            .map(|block| {
+                let indices = block
+                    .transactions
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, t)| self.matches_with_statistics(t, context, statistics))
+                    .map(|(i, _)| i)
+                    .collect::<Vec<_>>();
+                FilteredBlockTransaction {
+                    block: block.clone(),
+                    indices: Rc::new(indices),
+                }
+            })
+            .collect()
+    }
+
+    /// 求值，返回None表示该子树为空，不构成约束
+    fn evaluate(
+        &self,
+        transaction: &ParsedVersionedTransactionWithStatusMeta,
+        context: &mut TransactionFilterContext,
+        mut statistics: Option<&mut TransactionFilterStatistics>,
+    ) -> Option<bool> {
+        match self {
+            TransactionFilterExpression::Filter { id, filter } => {
+                let passed = filter.filter(transaction, context);
+                if let Some(statistics) = statistics {
+                    statistics.record(*id, passed);
+                }
+                Some(passed)
+            }
+            TransactionFilterExpression::And(children) => {
+                let mut ret = None;
+                for child in children {
+                    match child.evaluate(transaction, context, statistics.as_deref_mut()) {
+                        Some(false) => return Some(false),
+                        Some(true) => ret = Some(true),
+                        None => {}
+                    }
+                }
+                ret
+            }
+            TransactionFilterExpression::Or(children) => {
+                let mut ret = None;
+                for child in children {
+                    match child.evaluate(transaction, context, statistics.as_deref_mut()) {
+                        Some(true) => return Some(true),
+                        Some(false) => ret = Some(false),
+                        None => {}
+                    }
+                }
+                ret
+            }
+            TransactionFilterExpression::Not(inner) => {
+                inner.evaluate(transaction, context, statistics).map(|r| !r)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::transaction_filter::range_filter::{FeeRangeFilter, U64Range};
+    use serde_json::json;
+
+    const FEE: u64 = 5_000;
+
+    fn transaction() -> ParsedVersionedTransactionWithStatusMeta {
+        let transaction = serde_json::from_value(json!({
+            "transaction": {
+                "signatures": [
+                    "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW"
+                ],
+                "message": {
+                    "header": {
+                        "numRequiredSignatures": 1,
+                        "numReadonlySignedAccounts": 0,
+                        "numReadonlyUnsignedAccounts": 1
+                    },
+                    "accountKeys": [
+                        "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU",
+                        "ComputeBudget111111111111111111111111111111"
+                    ],
+                    "recentBlockhash": "EkSnNWid2cvwEVnVx9aBqawnmiCNiDgp3gUdkDPTKN1N",
+                    "instructions": [
+                        {"programIdIndex": 1, "accounts": [], "data": "3DdGGhkhJbjm", "stackHeight": null}
+                    ]
+                }
+            },
+            "meta": {
+                "err": null,
+                "status": {"Ok": null},
+                "fee": FEE,
+                "preBalances": [1_000_000_000u64, 1],
+                "postBalances": [1_000_000_000u64 - FEE, 1],
+                "logMessages": [],
+                "preTokenBalances": [],
+                "postTokenBalances": [],
+                "rewards": []
+            },
+            "version": "legacy"
+        }))
+        .unwrap();
+        ParsedVersionedTransactionWithStatusMeta::from_encoded_transaction_with_status_meta(
+            transaction,
+            100,
+            None,
+        )
+    }
+
+    /// 构建一个结果固定的过滤器节点
+    fn fixed(passes: bool) -> TransactionFilterExpression {
+        let range = if passes {
+            U64Range::new(Some(FEE), Some(FEE))
+        } else {
+            U64Range::new(Some(FEE + 1), None)
+        };
+        TransactionFilterExpression::filter(FeeRangeFilter::new(range))
+    }
+
+    fn id_of(expression: &TransactionFilterExpression) -> TransactionFilterId {
+        expression.filter_ids()[0]
+    }
+
+    fn evaluate(expression: &TransactionFilterExpression) -> Option<bool> {
+        expression.evaluate(
+            &transaction(),
+            &mut TransactionFilterContext::default(),
+            None,
+        )
+    }
+
+    #[test]
+    fn and_or_not() {
+        use FilterGroupOperator::{And, Or};
+        let group = TransactionFilterExpression::group;
+        assert_eq!(
+            evaluate(&group(And, vec![fixed(true), fixed(true)])),
+            Some(true)
+        );
+        assert_eq!(
+            evaluate(&group(And, vec![fixed(true), fixed(false)])),
+            Some(false)
+        );
+        assert_eq!(
+            evaluate(&group(Or, vec![fixed(false), fixed(true)])),
+            Some(true)
+        );
+        assert_eq!(
+            evaluate(&group(Or, vec![fixed(false), fixed(false)])),
+            Some(false)
+        );
+        assert_eq!(evaluate(&!fixed(true)), Some(false));
+        assert_eq!(
+            evaluate(&!group(Or, vec![fixed(false), group(And, vec![fixed(true)])])),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn empty_groups_evaluate_to_none() {
+        use FilterGroupOperator::{And, Or};
+        let group = TransactionFilterExpression::group;
+        let empty_trees = [
+            group(And, vec![]),
+            group(Or, vec![]),
+            !group(And, vec![]),
+            group(And, vec![group(Or, vec![]), !group(And, vec![])]),
+        ];
+        for expression in &empty_trees {
+            assert!(expression.is_empty());
+            assert_eq!(evaluate(expression), None);
+            assert!(expression.matches(&transaction(), &mut TransactionFilterContext::default()));
+        }
+        // 空的子组不影响所在组的结果
+        assert_eq!(
+            evaluate(&group(And, vec![group(Or, vec![]), fixed(false)])),
+            Some(false)
+        );
+        assert_eq!(
+            evaluate(&group(Or, vec![group(And, vec![]), fixed(false)])),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn short_circuit_skips_statistics() {
+        let first = fixed(false);
+        let second = fixed(true);
+        let (first_id, second_id) = (id_of(&first), id_of(&second));
+        let expression =
+            TransactionFilterExpression::group(FilterGroupOperator::And, vec![first, second]);
+        let mut statistics = TransactionFilterStatistics::default();
+        let keep = expression.matches_with_statistics(
+            &transaction(),
+            &mut TransactionFilterContext::default(),
+            &mut statistics,
+        );
+        assert!(!keep);
+        assert_eq!(
+            statistics.get(&first_id),
+            Some(&FilterStatistic {
+                evaluated: 1,
+                passed: 0,
+                rejected: 1,
+            })
+        );
+        assert_eq!(statistics.get(&second_id), None);
+        assert_eq!((statistics.total, statistics.kept), (1, 0));
+
+        let first = fixed(true);
+        let second = fixed(false);
+        let (first_id, second_id) = (id_of(&first), id_of(&second));
+        let expression =
+            TransactionFilterExpression::group(FilterGroupOperator::Or, vec![first, second]);
+        let mut statistics = TransactionFilterStatistics::default();
+        let keep = expression.matches_with_statistics(
+            &transaction(),
+            &mut TransactionFilterContext::default(),
+            &mut statistics,
+        );
+        assert!(keep);
+        assert_eq!(statistics.get(&first_id).map(|s| s.passed), Some(1));
+        assert_eq!(statistics.get(&second_id), None);
+        assert_eq!((statistics.total, statistics.kept), (1, 1));
+    }
+
+    #[test]
+    fn remove_filter_by_id_prunes_empty_groups() {
+        use FilterGroupOperator::{And, Or};
+        let kept = fixed(true);
+        let removed = fixed(false);
+        let (kept_id, removed_id) = (id_of(&kept), id_of(&removed));
+        let mut expression = TransactionFilterExpression::group(
+            And,
+            vec![
+                kept,
+                TransactionFilterExpression::group(Or, vec![!removed]),
+            ],
+        );
+
+        assert!(!expression.remove_filter_by_id(TransactionFilterId::next()));
+        assert!(expression.remove_filter_by_id(removed_id));
+        assert!(!expression.remove_filter_by_id(removed_id));
+        assert_eq!(expression.filter_ids(), vec![kept_id]);
+        match &expression {
+            TransactionFilterExpression::And(children) => assert_eq!(children.len(), 1),
+            _ => panic!("根节点应当仍是且组"),
+        }
+
+        assert!(expression.remove_filter_by_id(kept_id));
+        assert!(expression.is_empty());
+        assert_eq!(evaluate(&expression), None);
+    }
+
+    #[test]
+    fn and_filter_wraps_non_and_root() {
+        let mut expression = fixed(false);
+        let root_id = id_of(&expression);
+        let id = expression.and_filter(Box::new(FeeRangeFilter::new(U64Range::default())));
+        assert_eq!(expression.filter_ids(), vec![root_id, id]);
+        assert_eq!(evaluate(&expression), Some(false));
+    }
+}
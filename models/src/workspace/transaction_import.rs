@@ -0,0 +1,163 @@
+use crate::workspace::cheap_block_transaction::{
+    CheapBlockTransaction, ParsedVersionedTransactionWithStatusMeta,
+};
+use anyhow::anyhow;
+use block_insight_cross::api::transaction::BlockTransaction;
+use serde_json::Value;
+use solana_transaction_status_client_types::{
+    EncodedConfirmedTransactionWithStatusMeta, UiConfirmedBlock,
+};
+
+/// 导入的数据
+#[derive(Debug, Clone)]
+pub enum ImportedData {
+    /// 单笔交易
+    Transaction(ParsedVersionedTransactionWithStatusMeta),
+    /// 区块或多笔交易，多笔交易会按slot归并为区块
+    Blocks(Vec<CheapBlockTransaction>),
+}
+
+/// 解析导出的JSON数据，支持:
+/// - getTransaction的结果(EncodedConfirmedTransactionWithStatusMeta)
+/// - getBlock的结果，其中没有slot字段，slot取自数据中额外添加的slot字段，
+///   没有时使用block_slot(来自用户输入或文件名)，只能用于一个区块，仍无法确定时返回错误。
+///   不能以parentSlot + 1代替，因为parentSlot的下一个slot可能被跳过
+/// - 以上数据组成的数组
+/// - 以上数据的JSON-RPC响应(包含result字段)
+pub fn parse_import(json: &str, block_slot: Option<u64>) -> anyhow::Result<ImportedData> {
+    let value: Value = serde_json::from_str(json)?;
+    let value = unwrap_rpc_response(value);
+    let mut block_slot = block_slot;
+    match value {
+        Value::Array(items) => {
+            let mut blocks = Vec::new();
+            let mut transactions = Vec::new();
+            for item in items {
+                match parse_item(unwrap_rpc_response(item), &mut block_slot)? {
+                    ImportedData::Transaction(tx) => transactions.push(tx),
+                    ImportedData::Blocks(b) => blocks.extend(b),
+                }
+            }
+            blocks.extend(CheapBlockTransaction::group_by_slot(transactions));
+            blocks.sort_by_key(|b| b.slot);
+            Ok(ImportedData::Blocks(blocks))
+        }
+        value => parse_item(value, &mut block_slot),
+    }
+}
+
+/// 从文件名中获取区块的slot，取文件名(不含扩展名)中最后一段连续的数字，如`block_301234567.json`
+pub fn slot_from_file_name(name: &str) -> Option<u64> {
+    let name = name.rsplit(['/', '\\']).next().unwrap_or(name);
+    let stem = name.rsplit_once('.').map_or(name, |(stem, _)| stem);
+    stem.split(|c: char| !c.is_ascii_digit())
+        .rfind(|s| !s.is_empty())
+        .and_then(|s| s.parse().ok())
+}
+
+fn unwrap_rpc_response(value: Value) -> Value {
+    match value {
+        Value::Object(mut object) if object.contains_key("jsonrpc") => {
+            object.remove("result").unwrap_or(Value::Null)
+        }
+        value => value,
+    }
+}
+
+fn parse_item(value: Value, block_slot: &mut Option<u64>) -> anyhow::Result<ImportedData> {
+    if value.get("transactions").is_some() {
+        let slot = value.get("slot").and_then(|s| s.as_u64());
+        let block: UiConfirmedBlock = serde_json::from_value(value)?;
+        let slot = slot.or_else(|| block_slot.take()).ok_or_else(|| {
+            anyhow!("区块数据中不包含slot，请填写区块的slot或在文件名中包含slot(如block_301234567.json)")
+        })?;
+        let block = BlockTransaction {
+            slot,
+            block_time: block.block_time,
+            block_height: block.block_height,
+            transactions: block.transactions,
+        };
+        return Ok(ImportedData::Blocks(vec![CheapBlockTransaction::from_block(
+            block,
+        )]));
+    }
+    if value.get("transaction").is_some() && value.get("slot").is_some() {
+        let transaction: EncodedConfirmedTransactionWithStatusMeta = serde_json::from_value(value)?;
+        return Ok(ImportedData::Transaction(
+            ParsedVersionedTransactionWithStatusMeta::new(transaction),
+        ));
+    }
+    Err(anyhow!("无法识别的数据格式，只支持getTransaction及getBlock的结果"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block_json(slot: Option<u64>) -> String {
+        let slot = slot.map(|s| format!(r#""slot": {s},"#)).unwrap_or_default();
+        format!(
+            r#"{{
+                {slot}
+                "blockhash": "5yGNjKZc8ZVgCHpBGkZn3ACHhJiAL7hQk2XUgcgbAKZA",
+                "previousBlockhash": "4sGjMW1sUnHzSxGspuhpqLDx6wiyjNtZAMdL4VZHirAn",
+                "parentSlot": 99,
+                "transactions": [],
+                "blockTime": 1700000000,
+                "blockHeight": 90
+            }}"#
+        )
+    }
+
+    fn block_slots(data: ImportedData) -> Vec<u64> {
+        match data {
+            ImportedData::Blocks(blocks) => blocks.iter().map(|b| b.slot).collect(),
+            ImportedData::Transaction(_) => panic!("应为区块"),
+        }
+    }
+
+    #[test]
+    fn block_without_slot_is_rejected() {
+        assert!(parse_import(&block_json(None), None).is_err());
+    }
+
+    #[test]
+    fn block_slot_comes_from_data_or_caller() {
+        let data = parse_import(&block_json(Some(120)), Some(200)).unwrap();
+        assert_eq!(block_slots(data), vec![120]);
+        let data = parse_import(&block_json(None), Some(200)).unwrap();
+        assert_eq!(block_slots(data), vec![200]);
+    }
+
+    #[test]
+    fn unwraps_rpc_response() {
+        let json = format!(
+            r#"{{"jsonrpc": "2.0", "id": 1, "result": {}}}"#,
+            block_json(None)
+        );
+        let data = parse_import(&json, Some(200)).unwrap();
+        assert_eq!(block_slots(data), vec![200]);
+    }
+
+    #[test]
+    fn caller_slot_is_used_for_one_block_only() {
+        let json = format!("[{}, {}]", block_json(None), block_json(None));
+        assert!(parse_import(&json, Some(200)).is_err());
+        let json = format!("[{}, {}]", block_json(Some(300)), block_json(None));
+        let data = parse_import(&json, Some(200)).unwrap();
+        assert_eq!(block_slots(data), vec![200, 300]);
+    }
+
+    #[test]
+    fn unknown_format_is_rejected() {
+        assert!(parse_import(r#"{"foo": 1}"#, None).is_err());
+        assert!(parse_import("not json", None).is_err());
+    }
+
+    #[test]
+    fn slot_from_file_name_uses_last_number() {
+        assert_eq!(slot_from_file_name("block_301234567.json"), Some(301234567));
+        assert_eq!(slot_from_file_name("dumps/v2/301234567"), Some(301234567));
+        assert_eq!(slot_from_file_name("block.json"), None);
+    }
+}
@@ -1,25 +1,35 @@
 use crate::WorkspaceState;
-use crate::workspace::cheap_block_transaction::CheapBlockTransaction;
+use crate::network::solana_rpc::{self, RpcError};
+use crate::workspace::address_history::{AddressHistoryPage, AddressHistoryQuery};
+use crate::workspace::cheap_block_transaction::{
+    CheapBlockTransaction, FilteredBlockTransaction, ParsedVersionedTransactionWithStatusMeta,
+};
+use crate::workspace::persistent_block_cache::{
+    PersistentBlockCache, PersistentBlockCacheConfig,
+};
+use crate::workspace::transaction_cache::{
+    TransactionCache, TransactionCacheConfig, nearby_slot_range, push_slot,
+};
+use crate::workspace::transaction_filter_config::{FilterBuildError, FilterGroupConfig};
+use crate::workspace::transaction_filter_expression::{
+    BoxedTransactionFilter, TransactionFilterExpression, TransactionFilterId,
+    TransactionFilterStatistics,
+};
+use crate::workspace::transaction_import::{ImportedData, parse_import, slot_from_file_name};
+use crate::workspace::transaction_sort::TransactionSort;
+use crate::workspace::workspace_link::{LinkedQuery, WorkspaceLink};
 use anyhow::anyhow;
 use block_insight_cross::api::api_error::ApiError;
-use block_insight_cross::api::transaction::client::fetch_transactions_near_by;
-use block_insight_cross::api::transaction::{FetchTransactionParam, FetchTransactionsNearByParam};
-use block_insight_cross::parsed_instruction::{ParsedInstruction, ParsedInstructionList};
+use block_insight_cross::api::transaction::FetchTransactionParam;
 use block_insight_cross::transaction::transaction_filter::{
-    TransactionFilter, TransactionFilterContext, TransactionMeta, TransactionPropsProvider,
+    TransactionFilter, TransactionFilterContext,
 };
-use block_insight_cross::utils::TransactionAccounts;
+use dioxus::core::Task;
 use dioxus::hooks::UnboundedReceiver;
 use dioxus::logger::tracing::{error, info};
 use dioxus::prelude::*;
 use dioxus::prelude::{Coroutine, Signal, use_coroutine};
 use futures_util::StreamExt;
-use solana_transaction_status_client_types::{
-    EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction,
-    EncodedTransactionWithStatusMeta, UiMessage,
-};
-use std::any::TypeId;
-use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 use std::rc::Rc;
 use std::str::FromStr;
@@ -28,6 +38,10 @@ use std::str::FromStr;
 pub enum TransactionServiceModule {
     Query,
     QueryNearby,
+    QueryBlock,
+    AddressHistory,
+    CachedSlots,
+    Import,
 }
 
 impl Display for TransactionServiceModule {
@@ -39,108 +53,17 @@ impl Display for TransactionServiceModule {
             TransactionServiceModule::QueryNearby => {
                 write!(f, "查询邻近交易")
             }
-        }
-    }
-}
-
-#[derive(Debug, Clone, PartialEq)]
-pub struct ParsedEncodedConfirmedTransactionWithStatusMeta {
-    pub transaction: Rc<EncodedConfirmedTransactionWithStatusMeta>,
-    pub parsed_instructions: Rc<ParsedInstructionList>,
-}
-
-impl ParsedEncodedConfirmedTransactionWithStatusMeta {
-    pub fn new(transaction: EncodedConfirmedTransactionWithStatusMeta) -> Self {
-        let parsed_instructions = ParsedInstructionList::from(&transaction.transaction);
-        ParsedEncodedConfirmedTransactionWithStatusMeta {
-            transaction: Rc::new(transaction),
-            parsed_instructions: Rc::new(parsed_instructions),
-        }
-    }
-
-    pub fn from_encoded_transaction_with_status_meta(
-        transaction: EncodedTransactionWithStatusMeta,
-        slot: u64,
-        block_time: Option<i64>,
-    ) -> Self {
-        let parsed_instructions = Rc::new(ParsedInstructionList::from(&transaction));
-        let transaction = EncodedConfirmedTransactionWithStatusMeta {
-            slot,
-            transaction,
-            block_time,
-        };
-        let transaction = Rc::new(transaction);
-        ParsedEncodedConfirmedTransactionWithStatusMeta {
-            transaction,
-            parsed_instructions,
-        }
-    }
-}
-
-impl TransactionPropsProvider for ParsedEncodedConfirmedTransactionWithStatusMeta {
-    fn get_accounts(&self) -> TransactionAccounts<'_, String> {
-        let (w, r) = &self
-            .transaction
-            .transaction
-            .meta
-            .as_ref()
-            .map(|m| {
-                m.loaded_addresses
-                    .as_ref()
-                    .map(|l| (&l.writable, &l.readonly))
-            })
-            .flatten()
-            .map(|(w, r)| (Some(w), Some(r)))
-            .unwrap_or((None, None));
-        let account_keys = match &self.transaction.transaction.transaction {
-            EncodedTransaction::Json(tx) => match &tx.message {
-                UiMessage::Parsed(_) => {
-                    error!("当前只支持Raw格式的Message, 实际为Parsed");
-                    None
-                }
-                UiMessage::Raw(raw) => Some(raw.account_keys.as_slice()),
-            },
-            _ => {
-                error!("目前只支持Json格式的交易");
-                None
+            TransactionServiceModule::QueryBlock => {
+                write!(f, "按slot查询区块")
             }
-        };
-        TransactionAccounts::from_accounts(
-            account_keys,
-            w.map(|w| w.as_slice()),
-            r.map(|r| r.as_slice()),
-        )
-    }
-
-    fn get_signatures(&self) -> Option<&[String]> {
-        match &self.transaction.transaction.transaction {
-            EncodedTransaction::Json(tx) => Some(tx.signatures.as_ref()),
-            _ => {
-                error!("当前只支持Json格式的交易数据");
-                None
+            TransactionServiceModule::AddressHistory => {
+                write!(f, "地址交易历史")
             }
-        }
-    }
-
-    fn get_parsed_instructions(&self) -> Option<&[ParsedInstruction]> {
-        Some(self.parsed_instructions.as_slice())
-    }
-
-    fn get_meta(&self) -> Option<TransactionMeta> {
-        match &self.transaction.transaction.meta {
-            None => None,
-            Some(m) => {
-                let meta = TransactionMeta {
-                    err: m.err.as_ref(),
-                    status: &m.status,
-                    fee: m.fee,
-                    pre_balances: m.pre_balances.as_slice(),
-                    post_balances: m.post_balances.as_slice(),
-                    log_messages: m.log_messages.as_ref().map(|m| m.as_slice()),
-                    compute_units_consumed: m.compute_units_consumed.as_ref().map(|c| *c),
-                };
-
-                Some(meta)
+            TransactionServiceModule::CachedSlots => {
+                write!(f, "本地缓存")
+            }
+            TransactionServiceModule::Import => {
+                write!(f, "导入数据")
             }
         }
     }
@@ -149,12 +72,19 @@ impl TransactionPropsProvider for ParsedEncodedConfirmedTransactionWithStatusMet
 // #[derive(Clone)]
 #[derive(Debug, Clone)]
 pub enum HandlingData {
-    Query(ParsedEncodedConfirmedTransactionWithStatusMeta),
+    Query(ParsedVersionedTransactionWithStatusMeta),
     QueryNearby(Rc<Vec<CheapBlockTransaction>>),
 }
 
+/// 过滤后的用户可见数据，邻近交易只保存下标，交易数据与[HandlingData]共享
+#[derive(Debug, Clone)]
+pub enum FilteredHandlingData {
+    Query(ParsedVersionedTransactionWithStatusMeta),
+    QueryNearby(Rc<Vec<FilteredBlockTransaction>>),
+}
+
 // pub enum WorkspaceData {
-//     TransactionInspection(ParsedEncodedConfirmedTransactionWithStatusMeta),
+//     TransactionInspection(ParsedVersionedTransactionWithStatusMeta),
 //     TransactionsAnalyzing {
 //         current: Option<Rc<ParsedEncodedTransactionWithStatusMeta>>,
 //         all: Rc<Vec<CheapBlockTransaction>>,
@@ -215,7 +145,12 @@ impl InspectingDataStatus {
 }
 
 pub enum InspectingData {
-    SingleTransaction(ParsedEncodedConfirmedTransactionWithStatusMeta),
+    SingleTransaction(ParsedVersionedTransactionWithStatusMeta),
+    /// 并排对比两笔交易
+    Comparison {
+        left: ParsedVersionedTransactionWithStatusMeta,
+        right: ParsedVersionedTransactionWithStatusMeta,
+    },
 }
 
 #[derive(Clone, Debug)]
@@ -223,6 +158,25 @@ pub enum TransactionServiceStatus {
     Idle,
     Processing(TransactionServiceModule),
     Finish(TransactionServiceModule),
+    /// 查询被取消或被更新的查询取代
+    Cancelled(TransactionServiceModule),
+}
+
+/// 交易服务的错误，按slot查询区块及地址历史直接请求Solana节点，其余请求服务端
+#[derive(Debug, thiserror::Error)]
+pub enum TransactionServiceError {
+    #[error("服务端错误: {0:?}")]
+    Api(ApiError),
+    #[error(transparent)]
+    Rpc(#[from] RpcError),
+    #[error("未找到交易: {0}")]
+    TransactionNotFound(String),
+}
+
+impl From<ApiError> for TransactionServiceError {
+    fn from(e: ApiError) -> Self {
+        TransactionServiceError::Api(e)
+    }
 }
 
 #[derive(Clone, Copy, PartialEq)]
@@ -234,64 +188,98 @@ pub struct TransactionServiceState {
     /// 当前正在处理的数据
     pub handling_data: Signal<Option<HandlingData>>,
     /// 真正的正在处理的数据，即原原始数据上进行过滤筛选后得到数据集,即用户可见数据集
-    pub filtered_handling_data: Signal<Option<HandlingData>>,
+    pub filtered_handling_data: Signal<Option<FilteredHandlingData>>,
     /// 正在进行检视的数据,用户选择的正在进行详细检视的数据
     pub inspecting_data: Signal<Option<InspectingDataStatus>>,
     /// 当前错误
-    pub transaction_service_error: Signal<Option<ApiError>>,
+    pub transaction_service_error: Signal<Option<TransactionServiceError>>,
     /// 当前的过滤上下文
     pub transaction_filter_context: Signal<TransactionFilterContext>,
-    /// 过滤器集合
-    pub transaction_filters:
-        Signal<HashMap<TypeId, Box<dyn TransactionFilter<ContextType = TransactionFilterContext>>>>,
-    /// 附加过滤器，会依次对被transaction_filters过滤掉的结果再次过滤，如果符合条件，则会将此被排除的数据放回,不同的过滤器集合间为或的关系，但同一个集合中的过滤器间为且的关系
-    pub additional_filters: Signal<
-        Vec<HashMap<TypeId, Box<dyn TransactionFilter<ContextType = TransactionFilterContext>>>>,
-    >,
+    /// 过滤表达式树，可任意嵌套且/或/非
+    pub transaction_filter_expression: Signal<TransactionFilterExpression>,
+    /// 最近一次过滤的统计数据
+    pub transaction_filter_statistics: Signal<TransactionFilterStatistics>,
+    /// 交易列表的排序方式，重新过滤后依然有效
+    pub transaction_sort: Signal<TransactionSort>,
+    /// 最近一次加载的地址历史交易页，用于翻页
+    pub address_history_page: Signal<Option<AddressHistoryPage>>,
+    /// 按签名及slot缓存已加载的交易及区块
+    pub transaction_cache: Signal<TransactionCache>,
+    /// 保存在设备上的区块缓存，打开失败时为空
+    pub persistent_block_cache: Signal<Option<Rc<PersistentBlockCache>>>,
+    /// 最近一次导入数据的错误
+    pub transaction_import_error: Signal<Option<String>>,
+    /// 筛选面板的配置，与过滤表达式对应，用于恢复筛选面板及生成分享链接
+    pub transaction_filter_config: Signal<FilterGroupConfig>,
+    /// 最近一次应用筛选配置时无法构建的筛选项
+    pub transaction_filter_errors: Signal<Vec<FilterBuildError>>,
+    /// 当前数据对应的查询，数据无法通过查询复现(如导入的数据)时为空
+    pub linked_query: Signal<Option<LinkedQuery>>,
+    /// 通过链接打开时，数据加载完成后要检视的交易签名
+    pending_selection: Signal<Option<String>>,
     // pub filters:
 }
 
 impl TransactionServiceState {
+    /// 以且的关系向根表达式追加一个过滤器，同类型的过滤器可以同时存在多个，返回新过滤器的标识
     pub fn insert_filter(
         &mut self,
         filter: impl TransactionFilter<ContextType = TransactionFilterContext> + 'static,
-    ) {
-        let id = filter.id();
-        let filter =
-            Box::new(filter) as Box<dyn TransactionFilter<ContextType = TransactionFilterContext>>;
-        self.transaction_filters.write().insert(id, filter);
+    ) -> TransactionFilterId {
+        let filter = Box::new(filter) as BoxedTransactionFilter;
+        self.transaction_filter_expression.write().and_filter(filter)
+    }
+
+    /// 移除指定标识的单个过滤器，返回是否找到并移除
+    pub fn remove_filter_by_id(&mut self, id: TransactionFilterId) -> bool {
+        self.transaction_filter_expression
+            .write()
+            .remove_filter_by_id(id)
     }
 
+    /// 移除表达式树中所有与指定过滤器同类型的过滤器
     pub fn remove_filter(
         &mut self,
         filter: impl TransactionFilter<ContextType = TransactionFilterContext> + 'static,
     ) {
         let id = filter.id();
-        self.transaction_filters.write().remove(&id);
+        self.transaction_filter_expression
+            .write()
+            .remove_filters_by_type(id);
     }
 
-    pub fn set_and_apply_filters(
-        &mut self,
-        main_filters: HashMap<
-            TypeId,
-            Box<dyn TransactionFilter<ContextType = TransactionFilterContext>>,
-        >,
-        additional_filters: Vec<
-            HashMap<TypeId, Box<dyn TransactionFilter<ContextType = TransactionFilterContext>>>,
-        >,
-    ) {
-        self.transaction_filters.set(main_filters);
-        self.additional_filters.set(additional_filters);
+    pub fn set_and_apply_filters(&mut self, expression: TransactionFilterExpression) {
+        self.transaction_filter_expression.set(expression);
         self.apply_filters();
     }
 
+    /// 保存筛选面板的配置，并以其构建的表达式重新过滤，无法构建的筛选项记录在transaction_filter_errors中
+    pub fn set_and_apply_filter_config(&mut self, config: FilterGroupConfig) {
+        let mut errors = Vec::new();
+        let expression = config.to_expression(&mut errors);
+        self.transaction_filter_errors.set(errors);
+        self.transaction_filter_config.set(config);
+        self.set_and_apply_filters(expression);
+    }
+
+    /// 修改排序方式，只对已过滤的数据重新排序，不会重新过滤
+    pub fn set_sort(&mut self, sort: TransactionSort) {
+        self.transaction_sort.set(sort);
+        let filtered = self.filtered_handling_data.peek_unchecked().clone();
+        if let Some(FilteredHandlingData::QueryNearby(blocks)) = filtered {
+            let mut blocks = (*blocks).clone();
+            sort.sort_blocks(&mut blocks);
+            self.filtered_handling_data
+                .set(Some(FilteredHandlingData::QueryNearby(Rc::new(blocks))));
+        }
+    }
+
     pub(super) fn new() -> Self {
         let handling_data = use_signal(|| None);
         let real_handling_data = use_signal(|| None);
         let transaction_service_error = use_signal(|| None);
-        let transaction_filter = use_signal(|| HashMap::new());
+        let transaction_filter_expression = use_signal(|| TransactionFilterExpression::default());
         let inspecting_data = use_signal(|| None);
-        let additional_filters = use_signal(|| Vec::new());
 
         let mut state = TransactionServiceState {
             transaction_focus: use_signal(|| TransactionServiceModule::Query),
@@ -301,8 +289,17 @@ impl TransactionServiceState {
             inspecting_data,
             transaction_service_error,
             transaction_filter_context: use_signal(|| TransactionFilterContext::default()),
-            transaction_filters: transaction_filter,
-            additional_filters,
+            transaction_filter_expression,
+            transaction_filter_statistics: use_signal(|| TransactionFilterStatistics::default()),
+            transaction_sort: use_signal(|| TransactionSort::default()),
+            address_history_page: use_signal(|| None),
+            transaction_cache: use_signal(|| TransactionCache::default()),
+            persistent_block_cache: use_signal(|| None),
+            transaction_import_error: use_signal(|| None),
+            transaction_filter_config: use_signal(|| FilterGroupConfig::default()),
+            transaction_filter_errors: use_signal(Vec::new),
+            linked_query: use_signal(|| None),
+            pending_selection: use_signal(|| None),
         };
         state
     }
@@ -317,66 +314,389 @@ impl TransactionServiceState {
             .set(TransactionServiceStatus::Processing(
                 TransactionServiceModule::QueryNearby,
             ));
-        let ret = fetch_transactions_near_by(FetchTransactionsNearByParam {
-            signature: signature.clone(),
-            backward,
-            forward,
-        })
+        // 先确定目标交易所在的slot，再按slot窗口获取区块，窗口中已缓存的slot不再请求
+        let ret = match self.fetch_transaction(signature).await {
+            Ok(current) => {
+                let (start_slot, end_slot) = nearby_slot_range(current.slot(), backward, forward);
+                self.fetch_slots(start_slot, end_slot).await.map(|blocks| {
+                    // 优先使用区块中的交易，与列表中的交易保持一致
+                    let signature = current.signature().copied();
+                    let current = blocks
+                        .iter()
+                        .flat_map(|b| b.transactions.iter())
+                        .find(|tx| signature.is_some() && tx.signature() == signature.as_ref())
+                        .cloned()
+                        .unwrap_or(current);
+                    (blocks, Some(current))
+                })
+            }
+            Err(e) => Err(e),
+        };
+        match ret {
+            Ok((blocks, current)) => {
+                self.set_blocks(blocks, current);
+            }
+            Err(e) => {
+                self.transaction_service_error.set(Some(e));
+                self.handling_data.set(None);
+                self.filtered_handling_data.set(None);
+            }
+        }
+        self.transaction_service_status
+            .set(TransactionServiceStatus::Finish(
+                TransactionServiceModule::QueryNearby,
+            ))
+    }
+
+    async fn execute_query_block(&mut self, start_slot: u64, end_slot: Option<u64>) {
+        self.transaction_service_status
+            .set(TransactionServiceStatus::Processing(
+                TransactionServiceModule::QueryBlock,
+            ));
+        let ret = self
+            .fetch_slots(start_slot, end_slot.unwrap_or(start_slot))
+            .await;
+        match ret {
+            Ok(blocks) => {
+                self.set_blocks(blocks, None);
+            }
+            Err(e) => {
+                error!("error when req: {e:?}");
+                self.transaction_service_error.set(Some(e));
+                self.handling_data.set(None);
+                self.filtered_handling_data.set(None);
+            }
+        }
+        self.transaction_service_status
+            .set(TransactionServiceStatus::Finish(
+                TransactionServiceModule::QueryBlock,
+            ))
+    }
+
+    async fn execute_query_address_history(&mut self, query: AddressHistoryQuery) {
+        self.transaction_service_status
+            .set(TransactionServiceStatus::Processing(
+                TransactionServiceModule::AddressHistory,
+            ));
+        let ret = solana_rpc::fetch_transactions_for_address(
+            &query.address,
+            query.before.as_deref(),
+            query.until.as_deref(),
+            query.limit,
+        )
         .await;
         match ret {
             Ok(resp) => {
-                let resp = resp.expect("交易块数据必须不为空");
-                let mut current = None;
-                let raw_resp = resp
+                // 节点按时间从新到旧返回
+                let transactions = resp
                     .into_iter()
-                    .map(|ret| {
-                        let (cheap, temp) =
-                            CheapBlockTransaction::from_block_transaction(ret, &signature);
-                        if temp.is_some() {
-                            current = temp;
-                        }
-                        cheap
-                    })
-                    .collect::<Vec<_>>();
-                // 应用过滤器
-                let mut context = TransactionFilterContext::default();
-                let additional_filters = self.additional_filters.peek_unchecked();
-                let additional_filters = additional_filters
-                    .iter()
-                    .filter(|f| !f.is_empty())
+                    .map(ParsedVersionedTransactionWithStatusMeta::new)
                     .collect::<Vec<_>>();
-                let data = Self::do_apply_filters(
-                    &raw_resp,
-                    &mut context,
-                    &*self.transaction_filters.peek_unchecked(),
-                    &additional_filters,
-                );
-                info!("after filter , len: {}", data.len());
-                self.handling_data
-                    .set(Some(HandlingData::QueryNearby(Rc::new(raw_resp))));
-                self.filtered_handling_data
-                    .set(Some(HandlingData::QueryNearby(Rc::new(data))));
-                // todo: 未处理当前焦点数据是否符合要求
-                self.inspecting_data.set(
-                    current.map(|c| {
-                        InspectingDataStatus::Active(InspectingData::SingleTransaction(c))
-                    }),
-                );
-                self.transaction_filter_context.set(context);
-                self.transaction_service_error.set(None);
+                {
+                    let mut cache = self.transaction_cache.write();
+                    for tx in transactions.iter() {
+                        cache.insert_transaction(tx.clone());
+                    }
+                }
+                let signature_of = |tx: &ParsedVersionedTransactionWithStatusMeta| {
+                    tx.signature().map(|s| s.to_string())
+                };
+                let page = AddressHistoryPage {
+                    newest_signature: transactions.first().and_then(signature_of),
+                    oldest_signature: transactions.last().and_then(signature_of),
+                    count: transactions.len(),
+                    query,
+                };
+                let blocks = CheapBlockTransaction::group_by_slot(transactions.into_iter().rev());
+                self.set_blocks(blocks, None);
+                self.address_history_page.set(Some(page));
             }
             Err(e) => {
-                self.transaction_service_error.set(Some(e));
+                error!("error when req: {e:?}");
+                self.transaction_service_error.set(Some(e.into()));
                 self.handling_data.set(None);
                 self.filtered_handling_data.set(None);
+                self.address_history_page.set(None);
             }
         }
         self.transaction_service_status
             .set(TransactionServiceStatus::Finish(
-                TransactionServiceModule::QueryNearby,
+                TransactionServiceModule::AddressHistory,
             ))
     }
 
+    /// 只请求当前窗口之外的slot，新区块按slot顺序并入已有数据，过滤器只应用于新区块，
+    /// 正在检视的交易保持不变
+    async fn execute_extend_window(&mut self, direction: WindowDirection, slots: u64) {
+        let range = match &*self.handling_data.peek() {
+            Some(HandlingData::QueryNearby(blocks)) => {
+                let first = blocks.iter().map(|b| b.slot).min();
+                let last = blocks.iter().map(|b| b.slot).max();
+                match (direction, first, last) {
+                    (WindowDirection::Backward, Some(first), _) if first > 0 => {
+                        Some((first.saturating_sub(slots), first - 1))
+                    }
+                    (WindowDirection::Forward, _, Some(last)) => {
+                        Some((last + 1, last.saturating_add(slots)))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        let Some((start_slot, end_slot)) = range else {
+            info!("当前没有可扩展的区块列表");
+            return;
+        };
+
+        let module = *self.transaction_focus.peek();
+        self.transaction_service_status
+            .set(TransactionServiceStatus::Processing(module));
+        let ret = self.fetch_slots(start_slot, end_slot).await;
+        match ret {
+            Ok(blocks) => {
+                self.merge_blocks(blocks);
+                if let Some(query) = self.linked_query.write().as_mut() {
+                    query.extend(direction, slots);
+                }
+            }
+            Err(e) => {
+                error!("error when req: {e:?}");
+                // 扩展失败不影响已加载的数据
+                self.transaction_service_error.set(Some(e));
+            }
+        }
+        self.transaction_service_status
+            .set(TransactionServiceStatus::Finish(module));
+    }
+
+    /// 获取[start_slot, end_slot]范围内的区块，依次查询内存缓存、设备上的缓存，只请求都缺失的slot，
+    /// 返回的区块按slot升序排列
+    async fn fetch_slots(
+        &mut self,
+        start_slot: u64,
+        end_slot: u64,
+    ) -> Result<Vec<CheapBlockTransaction>, TransactionServiceError> {
+        let (mut blocks, missing) = self
+            .transaction_cache
+            .write()
+            .lookup_slots(start_slot, end_slot);
+        let persistent = self.persistent_block_cache.peek().clone();
+        let mut remote = Vec::new();
+        for (start, end) in missing {
+            for slot in start..=end {
+                let block = match persistent.as_ref() {
+                    Some(persistent) => persistent.load_block(slot).await,
+                    None => None,
+                };
+                let skipped = match (&block, persistent.as_ref()) {
+                    (None, Some(persistent)) => persistent.is_skipped_slot(slot).await,
+                    _ => false,
+                };
+                let mut cache = self.transaction_cache.write();
+                match block {
+                    Some(block) => {
+                        cache.record_persistent_hit();
+                        cache.insert_block(block.clone());
+                        blocks.push(block);
+                    }
+                    None if skipped => {
+                        cache.record_persistent_hit();
+                        cache.insert_skipped_slot(slot);
+                    }
+                    None => push_slot(&mut remote, slot),
+                }
+            }
+        }
+        for (start, end) in remote {
+            let fetched = solana_rpc::fetch_blocks(start, end).await?;
+            let skipped = fetched.skipped;
+            let fetched = fetched
+                .blocks
+                .into_iter()
+                .map(CheapBlockTransaction::from_block)
+                .collect::<Vec<_>>();
+            self.cache_blocks(&fetched, &skipped);
+            self.persist_blocks(&fetched, &skipped).await;
+            blocks.extend(fetched);
+        }
+        blocks.sort_by_key(|b| b.slot);
+        Ok(blocks)
+    }
+
+    /// 将从节点获取的区块及被跳过的slot保存到设备上，并按配置淘汰旧数据，范围内没有区块时同样保存被跳过的slot
+    async fn persist_blocks(&self, blocks: &[CheapBlockTransaction], skipped: &[u64]) {
+        let Some(persistent) = self.persistent_block_cache.peek().clone() else {
+            return;
+        };
+        for slot in skipped {
+            persistent.save_skipped_slot(*slot).await;
+        }
+        if blocks.is_empty() {
+            return;
+        }
+        for block in blocks {
+            persistent.save_block(block).await;
+        }
+        persistent.evict().await;
+    }
+
+    /// 只从设备上的缓存加载区块，不访问服务端
+    async fn execute_open_cached_slots(&mut self, start_slot: u64, end_slot: u64) {
+        let module = TransactionServiceModule::CachedSlots;
+        self.transaction_service_status
+            .set(TransactionServiceStatus::Processing(module));
+        let persistent = self.persistent_block_cache.peek().clone();
+        match persistent {
+            Some(persistent) => {
+                let mut blocks = Vec::new();
+                for cached in persistent.cached_slots().await {
+                    if cached.slot < start_slot || cached.slot > end_slot {
+                        continue;
+                    }
+                    if let Some(block) = persistent.load_block(cached.slot).await {
+                        self.transaction_cache.write().insert_block(block.clone());
+                        blocks.push(block);
+                    }
+                }
+                self.set_blocks(blocks, None);
+            }
+            None => {
+                error!("本地缓存不可用");
+            }
+        }
+        self.transaction_service_status
+            .set(TransactionServiceStatus::Finish(module));
+    }
+
+    /// 缓存从节点获取的区块及节点确认被跳过的slot
+    fn cache_blocks(&mut self, blocks: &[CheapBlockTransaction], skipped: &[u64]) {
+        let mut cache = self.transaction_cache.write();
+        for slot in skipped {
+            cache.insert_skipped_slot(*slot);
+        }
+        for block in blocks {
+            cache.insert_block(block.clone());
+        }
+    }
+
+    /// 修改缓存配置，超出新限制的缓存会被立即淘汰
+    pub fn set_cache_config(&mut self, config: TransactionCacheConfig) {
+        self.transaction_cache.write().set_config(config);
+    }
+
+    /// 将新区块并入当前数据，已存在的slot会被忽略
+    fn merge_blocks(&mut self, blocks: Vec<CheapBlockTransaction>) {
+        let Some(HandlingData::QueryNearby(current)) = self.handling_data.peek().clone() else {
+            return;
+        };
+        let mut new_blocks = blocks
+            .into_iter()
+            .filter(|b| current.iter().all(|c| c.slot != b.slot))
+            .collect::<Vec<_>>();
+        new_blocks.sort_by_key(|b| b.slot);
+
+        let mut statistics = TransactionFilterStatistics::default();
+        let filtered_new = Self::do_apply_filters(
+            &new_blocks,
+            &mut *self.transaction_filter_context.write(),
+            &mut statistics,
+            &*self.transaction_filter_expression.peek(),
+            &*self.transaction_sort.peek(),
+        );
+        let mut filtered = match &*self.filtered_handling_data.peek() {
+            Some(FilteredHandlingData::QueryNearby(filtered)) => (**filtered).clone(),
+            _ => Vec::new(),
+        };
+        filtered.extend(filtered_new);
+        filtered.sort_by_key(|b| b.slot());
+
+        let mut all = (*current).clone();
+        all.extend(new_blocks);
+        all.sort_by_key(|b| b.slot);
+
+        self.handling_data
+            .set(Some(HandlingData::QueryNearby(Rc::new(all))));
+        self.filtered_handling_data
+            .set(Some(FilteredHandlingData::QueryNearby(Rc::new(filtered))));
+        self.transaction_filter_statistics.write().merge(statistics);
+        self.transaction_service_error.set(None);
+    }
+
+    /// 导入导出的JSON数据，单笔交易作为查询结果，区块作为区块列表，导入的数据会加入内存缓存
+    async fn execute_import(&mut self, name: String, content: String, slot: Option<u64>) {
+        let module = TransactionServiceModule::Import;
+        self.transaction_service_status
+            .set(TransactionServiceStatus::Processing(module));
+        let slot = slot.or_else(|| slot_from_file_name(&name));
+        match parse_import(&content, slot) {
+            Ok(ImportedData::Transaction(data)) => {
+                self.transaction_cache
+                    .write()
+                    .insert_transaction(data.clone());
+                self.transaction_service_error.set(None);
+                self.address_history_page.set(None);
+                self.handling_data
+                    .set(Some(HandlingData::Query(data.clone())));
+                self.filtered_handling_data
+                    .set(Some(FilteredHandlingData::Query(data.clone())));
+                self.inspecting_data.set(Some(InspectingDataStatus::Active(
+                    InspectingData::SingleTransaction(data),
+                )));
+                self.transaction_import_error.set(None);
+            }
+            Ok(ImportedData::Blocks(blocks)) => {
+                {
+                    let mut cache = self.transaction_cache.write();
+                    for block in blocks.iter() {
+                        cache.insert_block(block.clone());
+                    }
+                }
+                info!("导入: {name}, 共{}个区块", blocks.len());
+                self.set_blocks(blocks, None);
+                self.transaction_import_error.set(None);
+            }
+            Err(e) => {
+                error!("导入: {name} 失败: {e}");
+                self.transaction_import_error
+                    .set(Some(format!("导入: {name} 失败: {e}")));
+            }
+        }
+        self.transaction_service_status
+            .set(TransactionServiceStatus::Finish(module));
+    }
+
+    /// 以新的区块列表替换当前数据并应用过滤器，current为要检视的交易
+    fn set_blocks(
+        &mut self,
+        blocks: Vec<CheapBlockTransaction>,
+        current: Option<ParsedVersionedTransactionWithStatusMeta>,
+    ) {
+        let mut context = TransactionFilterContext::default();
+        let mut statistics = TransactionFilterStatistics::default();
+        let data = Self::do_apply_filters(
+            &blocks,
+            &mut context,
+            &mut statistics,
+            &*self.transaction_filter_expression.peek_unchecked(),
+            &*self.transaction_sort.peek_unchecked(),
+        );
+        info!("after filter , len: {}", data.len());
+        let current = current.or_else(|| self.take_pending_selection(&blocks));
+        self.handling_data
+            .set(Some(HandlingData::QueryNearby(Rc::new(blocks))));
+        self.filtered_handling_data
+            .set(Some(FilteredHandlingData::QueryNearby(Rc::new(data))));
+        // todo: 未处理当前焦点数据是否符合要求
+        self.inspecting_data.set(
+            current.map(|c| InspectingDataStatus::Active(InspectingData::SingleTransaction(c))),
+        );
+        self.transaction_filter_context.set(context);
+        self.transaction_filter_statistics.set(statistics);
+        self.transaction_service_error.set(None);
+        self.address_history_page.set(None);
+    }
+
     async fn execute_query(&mut self, signature: String) {
         // 修改状态
         self.transaction_service_status
@@ -384,20 +704,17 @@ impl TransactionServiceState {
                 TransactionServiceModule::Query,
             ));
 
-        // 请求数据
-        let ret = block_insight_cross::api::transaction::client::fetch_transaction(
-            FetchTransactionParam { signature },
-        )
-        .await;
+        // 请求数据，优先使用缓存
+        let ret = self.fetch_transaction(signature).await;
         match ret {
-            Ok(resp) => {
-                let resp = resp.expect("数据不能为空");
+            Ok(data) => {
                 self.transaction_service_error.set(None);
-                let data = ParsedEncodedConfirmedTransactionWithStatusMeta::new(resp);
+                self.address_history_page.set(None);
+                self.pending_selection.set(None);
                 self.handling_data
                     .set(Some(HandlingData::Query(data.clone())));
                 self.filtered_handling_data
-                    .set(Some(HandlingData::Query(data.clone())));
+                    .set(Some(FilteredHandlingData::Query(data.clone())));
             }
             Err(e) => {
                 error!("error when req: {e:?}");
@@ -414,6 +731,40 @@ impl TransactionServiceState {
             ));
     }
 
+    /// 获取单笔交易，优先使用缓存，服务端未找到交易时返回[TransactionServiceError::TransactionNotFound]
+    async fn fetch_transaction(
+        &mut self,
+        signature: String,
+    ) -> Result<ParsedVersionedTransactionWithStatusMeta, TransactionServiceError> {
+        if let Some(cached) = self.transaction_cache.write().get_transaction(&signature) {
+            return Ok(cached);
+        }
+        let resp =
+            block_insight_cross::api::transaction::client::fetch_transaction(FetchTransactionParam {
+                signature: signature.clone(),
+            })
+            .await?
+            .ok_or(TransactionServiceError::TransactionNotFound(signature))?;
+        let data = ParsedVersionedTransactionWithStatusMeta::new(resp);
+        self.transaction_cache
+            .write()
+            .insert_transaction(data.clone());
+        Ok(data)
+    }
+
+    /// 取出通过链接指定的待检视交易，并在区块中查找
+    fn take_pending_selection(
+        &mut self,
+        blocks: &[CheapBlockTransaction],
+    ) -> Option<ParsedVersionedTransactionWithStatusMeta> {
+        let signature = self.pending_selection.take()?;
+        blocks
+            .iter()
+            .flat_map(|b| b.transactions.iter())
+            .find(|tx| tx.signature().is_some_and(|s| s.to_string() == signature))
+            .cloned()
+    }
+
     fn apply_filters(&mut self) {
         match &*self.handling_data.peek_unchecked() {
             None => {}
@@ -421,33 +772,35 @@ impl TransactionServiceState {
                 HandlingData::Query(_) => {}
                 HandlingData::QueryNearby(data) => {
                     let mut ctx = TransactionFilterContext::default();
-                    let filters = &*self.transaction_filters.peek_unchecked();
-                    let additional_filters = &*self.additional_filters.peek_unchecked();
-                    let additional_filters = additional_filters
-                        .iter()
-                        // 过滤掉没有过滤器的容器，以避免干扰正常过滤
-                        .filter(|f| !f.is_empty())
-                        .collect::<Vec<_>>();
-                    let real_handling_data =
-                        Self::do_apply_filters(data, &mut ctx, filters, &additional_filters);
+                    let mut statistics = TransactionFilterStatistics::default();
+                    let expression = &*self.transaction_filter_expression.peek_unchecked();
+                    let real_handling_data = Self::do_apply_filters(
+                        data,
+                        &mut ctx,
+                        &mut statistics,
+                        expression,
+                        &*self.transaction_sort.peek_unchecked(),
+                    );
                     let inspecting_data = self.inspecting_data.write_unchecked().take();
-                    if let Some(mut inspecting_data) = inspecting_data {
-                        match inspecting_data.data_ref() {
+                    if let Some(inspecting_data) = inspecting_data {
+                        let is_active = match inspecting_data.data_ref() {
                             InspectingData::SingleTransaction(inspecting_tx) => {
-                                let is_active =
-                                    Self::filter_transaction(inspecting_tx, &mut ctx, filters)
-                                        || Self::additional_filter_transaction(
-                                            &inspecting_tx,
-                                            &mut ctx,
-                                            &additional_filters,
-                                        );
-                                let inspecting_data = inspecting_data.set_active_status(is_active);
-                                self.inspecting_data.set(Some(inspecting_data))
+                                Self::filter_transaction(inspecting_tx, &mut ctx, expression)
                             }
-                        }
+                            // 对比的两笔交易都符合条件时才处于激活状态
+                            InspectingData::Comparison { left, right } => {
+                                Self::filter_transaction(left, &mut ctx, expression)
+                                    && Self::filter_transaction(right, &mut ctx, expression)
+                            }
+                        };
+                        let inspecting_data = inspecting_data.set_active_status(is_active);
+                        self.inspecting_data.set(Some(inspecting_data))
                     }
                     self.filtered_handling_data
-                        .set(Some(HandlingData::QueryNearby(Rc::new(real_handling_data))));
+                        .set(Some(FilteredHandlingData::QueryNearby(Rc::new(
+                            real_handling_data,
+                        ))));
+                    self.transaction_filter_statistics.set(statistics);
                 }
             },
         }
@@ -456,76 +809,39 @@ impl TransactionServiceState {
     fn do_apply_filters(
         data: &[CheapBlockTransaction],
         context: &mut TransactionFilterContext,
-        filters: &HashMap<
-            TypeId,
-            Box<dyn TransactionFilter<ContextType = TransactionFilterContext>>,
-        >,
-        additional_filters: &[&HashMap<
-            TypeId,
-            Box<dyn TransactionFilter<ContextType = TransactionFilterContext>>,
-        >],
-    ) -> Vec<CheapBlockTransaction> {
-        if filters.is_empty() {
-            return data.to_vec();
-        }
-
-        let mut ret = Vec::new();
-        for d in data {
-            let r = d
-                .transactions
-                .iter()
-                .filter_map(|t| {
-                    let keep = Self::filter_transaction(t, context, filters);
-                    if keep {
-                        Some(t.clone())
-                    } else {
-                        // 应用附加过滤器
-                        if Self::additional_filter_transaction(t, context, additional_filters) {
-                            Some(t.clone())
-                        } else {
-                            None
-                        }
-                    }
-                })
-                .collect::<Vec<_>>();
-            let new = CheapBlockTransaction {
-                slot: d.slot,
-                block_time: d.block_time,
-                block_height: d.block_height,
-                transactions: Rc::new(r),
-            };
-            ret.push(new);
-        }
-        ret
+        statistics: &mut TransactionFilterStatistics,
+        expression: &TransactionFilterExpression,
+        sort: &TransactionSort,
+    ) -> Vec<FilteredBlockTransaction> {
+        let mut blocks = expression.filter_blocks(data, context, statistics);
+        sort.sort_blocks(&mut blocks);
+        blocks
     }
 
     fn filter_transaction(
-        transaction: &ParsedEncodedConfirmedTransactionWithStatusMeta,
+        transaction: &ParsedVersionedTransactionWithStatusMeta,
         context: &mut TransactionFilterContext,
-        filters: &HashMap<
-            TypeId,
-            Box<dyn TransactionFilter<ContextType = TransactionFilterContext>>,
-        >,
+        expression: &TransactionFilterExpression,
     ) -> bool {
-        filters
-            .iter()
-            .all(|(_, filter)| filter.filter(&*transaction, context))
+        expression.matches(transaction, context)
     }
+}
 
-    fn additional_filter_transaction(
-        transaction: &ParsedEncodedConfirmedTransactionWithStatusMeta,
-        context: &mut TransactionFilterContext,
-        filters: &[&HashMap<
-            TypeId,
-            Box<dyn TransactionFilter<ContextType = TransactionFilterContext>>,
-        >],
-    ) -> bool {
-        filters
-            .iter()
-            .any(|filter| Self::filter_transaction(&transaction, context, filter))
-    }
+/// 扩展邻近交易窗口时，每次默认加载的slot数
+pub const EXTEND_WINDOW_SLOTS: u64 = 10;
+
+/// 扩展窗口的方向
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum WindowDirection {
+    /// 加载更早的slot
+    Backward,
+    /// 加载更晚的slot
+    Forward,
 }
 
+/// 按slot查询区块时，一次最多查询的slot数
+pub const MAX_QUERY_BLOCK_SLOTS: u64 = 500;
+
 #[derive(Clone)]
 pub enum TransactionCmd {
     Query(String),
@@ -534,12 +850,97 @@ pub enum TransactionCmd {
         backward: Option<u32>,
         forward: Option<u32>,
     },
+    /// 按slot查询区块，end_slot不为空时查询[start_slot, end_slot]范围内的所有区块
+    QueryBlock {
+        start_slot: u64,
+        end_slot: Option<u64>,
+    },
+    /// 查询帐号地址的一页历史交易
+    AddressHistory(AddressHistoryQuery),
+    /// 在当前区块列表的前/后追加加载若干slot，已加载的数据保持不变
+    ExtendWindow {
+        direction: WindowDirection,
+        slots: u64,
+    },
+    /// 从设备上的缓存打开[start_slot, end_slot]范围内的区块，不访问服务端
+    OpenCachedSlots {
+        start_slot: u64,
+        end_slot: u64,
+    },
+    /// 导入导出的JSON数据，name为文件名，slot为用户指定的区块slot
+    Import {
+        name: String,
+        content: String,
+        slot: Option<u64>,
+    },
+    /// 取消正在进行的查询
+    Cancel,
 }
 
 impl TransactionServiceState {
     pub fn focus(&mut self, module: TransactionServiceModule) {
         self.transaction_focus.set(module);
     }
+
+    /// 检视单笔交易
+    pub fn inspect_transaction(&mut self, transaction: ParsedVersionedTransactionWithStatusMeta) {
+        self.inspecting_data.set(Some(InspectingDataStatus::Active(
+            InspectingData::SingleTransaction(transaction),
+        )));
+    }
+
+    /// 将交易与正在检视的交易并排对比，正在检视的交易作为左侧；
+    /// 正在对比时替换右侧的交易，没有正在检视的交易时直接检视该交易
+    pub fn compare_with_inspecting(&mut self, transaction: ParsedVersionedTransactionWithStatusMeta) {
+        let left = match self.inspecting_data.peek().as_ref().map(|d| d.data_ref()) {
+            Some(InspectingData::SingleTransaction(left))
+            | Some(InspectingData::Comparison { left, .. }) => Some(left.clone()),
+            None => None,
+        };
+        let data = match left {
+            Some(left) if left.signature() != transaction.signature() => InspectingData::Comparison {
+                left,
+                right: transaction,
+            },
+            _ => InspectingData::SingleTransaction(transaction),
+        };
+        self.inspecting_data
+            .set(Some(InspectingDataStatus::Active(data)));
+    }
+
+    /// 交换对比的左右两侧
+    pub fn swap_comparison(&mut self) {
+        let Some(status) = self.inspecting_data.write_unchecked().take() else {
+            return;
+        };
+        let status = status.map(|data| match data {
+            InspectingData::Comparison { left, right } => InspectingData::Comparison {
+                left: right,
+                right: left,
+            },
+            data => data,
+        });
+        self.inspecting_data.set(Some(status));
+    }
+
+    /// 是否有查询正在进行
+    pub fn is_processing(&self) -> bool {
+        matches!(
+            *self.transaction_service_status.read(),
+            TransactionServiceStatus::Processing(_)
+        )
+    }
+
+    /// 中止查询任务，如果查询尚未完成，则将状态置为已取消，当前数据保持不变
+    fn cancel_running(&mut self, task: Task) {
+        task.cancel();
+        let status = self.transaction_service_status.peek().clone();
+        if let TransactionServiceStatus::Processing(module) = status {
+            info!("查询被取消: {module}");
+            self.transaction_service_status
+                .set(TransactionServiceStatus::Cancelled(module));
+        }
+    }
 }
 
 impl WorkspaceState {
@@ -548,21 +949,87 @@ impl WorkspaceState {
     ) -> Coroutine<TransactionCmd> {
         let transaction_service =
             use_coroutine(move |mut r: UnboundedReceiver<TransactionCmd>| async move {
+                match PersistentBlockCache::open(PersistentBlockCacheConfig::default()).await {
+                    Ok(cache) => state.persistent_block_cache.set(Some(Rc::new(cache))),
+                    Err(e) => error!("打开本地区块缓存失败: {e}"),
+                }
+                // 正在进行的查询，新的命令总是取代它，被取消的查询不会再写入任何结果
+                let mut running: Option<Task> = None;
                 while let Some(cmd) = r.next().await {
-                    match cmd {
+                    if let Some(task) = running.take() {
+                        state.cancel_running(task);
+                    }
+                    match &cmd {
                         TransactionCmd::Query(signature) => {
-                            state.execute_query(signature).await;
+                            state.linked_query.set(Some(LinkedQuery::Transaction {
+                                signature: signature.clone(),
+                            }))
+                        }
+                        TransactionCmd::QueryNearBy {
+                            signature,
+                            backward,
+                            forward,
+                        } => state.linked_query.set(Some(LinkedQuery::Nearby {
+                            signature: signature.clone(),
+                            backward: *backward,
+                            forward: *forward,
+                        })),
+                        TransactionCmd::QueryBlock {
+                            start_slot,
+                            end_slot,
+                        } => state.linked_query.set(Some(LinkedQuery::Block {
+                            start_slot: *start_slot,
+                            end_slot: *end_slot,
+                        })),
+                        TransactionCmd::AddressHistory(query) => state
+                            .linked_query
+                            .set(Some(LinkedQuery::AddressHistory(query.clone()))),
+                        TransactionCmd::OpenCachedSlots { .. } | TransactionCmd::Import { .. } => {
+                            state.linked_query.set(None)
                         }
+                        TransactionCmd::ExtendWindow { .. } | TransactionCmd::Cancel => {}
+                    }
+                    let task = match cmd {
+                        TransactionCmd::Cancel => continue,
+                        TransactionCmd::Query(signature) => spawn(async move {
+                            state.execute_query(signature).await;
+                        }),
                         TransactionCmd::QueryNearBy {
                             signature,
                             backward,
                             forward,
-                        } => {
+                        } => spawn(async move {
                             state
                                 .execute_query_near_by(signature, backward, forward)
                                 .await;
-                        }
-                    }
+                        }),
+                        TransactionCmd::QueryBlock {
+                            start_slot,
+                            end_slot,
+                        } => spawn(async move {
+                            state.execute_query_block(start_slot, end_slot).await;
+                        }),
+                        TransactionCmd::AddressHistory(query) => spawn(async move {
+                            state.execute_query_address_history(query).await;
+                        }),
+                        TransactionCmd::ExtendWindow { direction, slots } => spawn(async move {
+                            state.execute_extend_window(direction, slots).await;
+                        }),
+                        TransactionCmd::Import {
+                            name,
+                            content,
+                            slot,
+                        } => spawn(async move {
+                            state.execute_import(name, content, slot).await;
+                        }),
+                        TransactionCmd::OpenCachedSlots {
+                            start_slot,
+                            end_slot,
+                        } => spawn(async move {
+                            state.execute_open_cached_slots(start_slot, end_slot).await;
+                        }),
+                    };
+                    running = Some(task);
                 }
             });
 
@@ -602,4 +1069,110 @@ impl WorkspaceState {
             Err(e) => Err(anyhow!(e)),
         }
     }
+
+    /// 按slot查询区块，end_slot为空时只查询start_slot所在的区块
+    pub fn query_block(&self, start_slot: u64, end_slot: Option<u64>) -> anyhow::Result<()> {
+        if let Some(end_slot) = end_slot {
+            if end_slot < start_slot {
+                return Err(anyhow!("结束slot: {end_slot} 不能小于起始slot: {start_slot}"));
+            }
+            if end_slot - start_slot >= MAX_QUERY_BLOCK_SLOTS {
+                return Err(anyhow!("一次最多查询{MAX_QUERY_BLOCK_SLOTS}个slot"));
+            }
+        }
+        let cmd = TransactionCmd::QueryBlock {
+            start_slot,
+            end_slot,
+        };
+        self.transaction_service.send(cmd);
+        Ok(())
+    }
+
+    /// 查询帐号地址的一页历史交易
+    pub fn query_address_history(&self, query: AddressHistoryQuery) -> anyhow::Result<()> {
+        query.validate()?;
+        self.transaction_service
+            .send(TransactionCmd::AddressHistory(query));
+        Ok(())
+    }
+
+    /// 取消正在进行的查询
+    pub fn cancel_query(&self) {
+        self.transaction_service.send(TransactionCmd::Cancel);
+    }
+
+    /// 在当前区块列表的前/后追加加载若干slot
+    pub fn extend_window(&self, direction: WindowDirection, slots: u64) {
+        self.transaction_service
+            .send(TransactionCmd::ExtendWindow { direction, slots });
+    }
+
+    /// 从设备上的缓存打开[start_slot, end_slot]范围内的区块
+    pub fn open_cached_slots(&self, start_slot: u64, end_slot: u64) {
+        self.transaction_service
+            .send(TransactionCmd::OpenCachedSlots {
+                start_slot,
+                end_slot,
+            });
+    }
+
+    /// 导入导出的JSON数据，不需要访问服务端。
+    /// getBlock的结果中没有slot，需由slot指定，为None时尝试从文件名中获取
+    pub fn import_json(&self, name: String, content: String, slot: Option<u64>) {
+        self.transaction_service.send(TransactionCmd::Import {
+            name,
+            content,
+            slot,
+        });
+    }
+
+    /// 生成复现当前视图的分享链接
+    pub fn share_link(&self) -> WorkspaceLink {
+        let state = &self.transaction_service_state;
+        let filters = state.transaction_filter_config.peek().clone();
+        let selected = state
+            .inspecting_data
+            .peek()
+            .as_ref()
+            .and_then(|data| match data.data_ref() {
+                InspectingData::SingleTransaction(tx)
+                | InspectingData::Comparison { left: tx, .. } => {
+                    tx.signature().map(|s| s.to_string())
+                }
+            });
+        WorkspaceLink {
+            query: state.linked_query.peek().clone(),
+            filters: (!filters.is_empty()).then_some(filters),
+            selected,
+        }
+    }
+
+    /// 打开分享链接: 恢复筛选条件并重新执行链接中的查询
+    pub fn open_link(&self, link: WorkspaceLink) {
+        let mut state = self.transaction_service_state;
+        if let Some(filters) = link.filters {
+            state.set_and_apply_filter_config(filters);
+        }
+        state.pending_selection.set(link.selected);
+        let Some(query) = link.query else {
+            return;
+        };
+        state.focus(query.module());
+        let ret = match query {
+            LinkedQuery::Transaction { signature } => self.query_transaction(signature),
+            LinkedQuery::Nearby {
+                signature,
+                backward,
+                forward,
+            } => self.query_transaction_near_by(signature, backward, forward),
+            LinkedQuery::Block {
+                start_slot,
+                end_slot,
+            } => self.query_block(start_slot, end_slot),
+            LinkedQuery::AddressHistory(query) => self.query_address_history(query),
+        };
+        if let Err(e) = ret {
+            error!("打开链接失败: {e}");
+        }
+    }
 }
@@ -0,0 +1,144 @@
+use crate::workspace::cheap_block_transaction::{
+    FilteredBlockTransaction, ParsedVersionedTransactionWithStatusMeta,
+};
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+
+/// 排序依据
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TransactionSortKey {
+    /// 交易在区块中的下标
+    Index,
+    Fee,
+    ComputeUnits,
+    /// 手续费支付者
+    Signer,
+    /// 成功/失败
+    Status,
+}
+
+impl TransactionSortKey {
+    pub const ALL: [TransactionSortKey; 5] = [
+        TransactionSortKey::Index,
+        TransactionSortKey::Fee,
+        TransactionSortKey::ComputeUnits,
+        TransactionSortKey::Signer,
+        TransactionSortKey::Status,
+    ];
+}
+
+impl Display for TransactionSortKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransactionSortKey::Index => write!(f, "区块内序号"),
+            TransactionSortKey::Fee => write!(f, "手续费"),
+            TransactionSortKey::ComputeUnits => write!(f, "消耗CU"),
+            TransactionSortKey::Signer => write!(f, "签名者"),
+            TransactionSortKey::Status => write!(f, "状态"),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+/// 排序范围
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TransactionSortScope {
+    /// 区块按获取顺序排列，只在区块内对交易排序
+    WithinBlock,
+    /// 将所有区块的交易展开为一个列表后排序
+    AcrossBlocks,
+}
+
+/// 展开视图中的一笔交易的位置
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TransactionPosition {
+    /// 所在区块在区块列表中的下标
+    pub block: usize,
+    /// 交易在原区块中的下标
+    pub index: usize,
+}
+
+/// 交易列表的排序方式，排序是稳定的，排序值相同的交易保持原有顺序
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TransactionSort {
+    pub key: TransactionSortKey,
+    pub direction: SortDirection,
+    pub scope: TransactionSortScope,
+}
+
+impl Default for TransactionSort {
+    fn default() -> Self {
+        TransactionSort {
+            key: TransactionSortKey::Index,
+            direction: SortDirection::Ascending,
+            scope: TransactionSortScope::WithinBlock,
+        }
+    }
+}
+
+impl TransactionSort {
+    /// 对每个区块内通过过滤的交易重新排序
+    pub fn sort_blocks(&self, blocks: &mut [FilteredBlockTransaction]) {
+        for block in blocks {
+            let mut indices = (*block.indices).clone();
+            let transactions = &block.block.transactions;
+            indices.sort_by(|&a, &b| {
+                self.compare((a, &transactions[a]), (b, &transactions[b]))
+            });
+            block.indices = Rc::new(indices);
+        }
+    }
+
+    /// 将所有区块中通过过滤的交易展开并排序
+    pub fn flatten(&self, blocks: &[FilteredBlockTransaction]) -> Vec<TransactionPosition> {
+        let mut positions = blocks
+            .iter()
+            .enumerate()
+            .flat_map(|(block, b)| {
+                b.indices
+                    .iter()
+                    .map(move |&index| TransactionPosition { block, index })
+            })
+            .collect::<Vec<_>>();
+        let get = |p: &TransactionPosition| {
+            let block = &blocks[p.block].block;
+            (p.index, &block.transactions[p.index])
+        };
+        positions.sort_by(|a, b| {
+            let ordering = self.compare(get(a), get(b));
+            // 按下标排序时，下标相同的交易按区块顺序排列
+            if ordering == Ordering::Equal && self.key == TransactionSortKey::Index {
+                a.block.cmp(&b.block)
+            } else {
+                ordering
+            }
+        });
+        positions
+    }
+
+    fn compare(
+        &self,
+        (a_index, a): (usize, &ParsedVersionedTransactionWithStatusMeta),
+        (b_index, b): (usize, &ParsedVersionedTransactionWithStatusMeta),
+    ) -> Ordering {
+        let ordering = match self.key {
+            TransactionSortKey::Index => a_index.cmp(&b_index),
+            TransactionSortKey::Fee => a.fee().cmp(&b.fee()),
+            TransactionSortKey::ComputeUnits => {
+                a.compute_units_consumed().cmp(&b.compute_units_consumed())
+            }
+            TransactionSortKey::Signer => a.fee_payer().cmp(&b.fee_payer()),
+            TransactionSortKey::Status => a.is_success().cmp(&b.is_success()),
+        };
+        match self.direction {
+            SortDirection::Ascending => ordering,
+            SortDirection::Descending => ordering.reverse(),
+        }
+    }
+}
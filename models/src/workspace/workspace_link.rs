@@ -0,0 +1,400 @@
+use crate::workspace::address_history::{AddressHistoryQuery, DEFAULT_ADDRESS_HISTORY_LIMIT};
+use crate::workspace::transaction_filter_config::FilterGroupConfig;
+use crate::workspace::transaction_service::{TransactionServiceModule, WindowDirection};
+use dioxus::logger::tracing::warn;
+use std::fmt::{Display, Formatter};
+
+/// 可以通过链接复现的查询
+#[derive(Clone, Debug, PartialEq)]
+pub enum LinkedQuery {
+    Transaction {
+        signature: String,
+    },
+    Nearby {
+        signature: String,
+        backward: Option<u32>,
+        forward: Option<u32>,
+    },
+    Block {
+        start_slot: u64,
+        end_slot: Option<u64>,
+    },
+    AddressHistory(AddressHistoryQuery),
+}
+
+impl LinkedQuery {
+    pub fn module(&self) -> TransactionServiceModule {
+        match self {
+            LinkedQuery::Transaction { .. } => TransactionServiceModule::Query,
+            LinkedQuery::Nearby { .. } => TransactionServiceModule::QueryNearby,
+            LinkedQuery::Block { .. } => TransactionServiceModule::QueryBlock,
+            LinkedQuery::AddressHistory(_) => TransactionServiceModule::AddressHistory,
+        }
+    }
+
+    /// 窗口扩展后同步更新查询范围，使链接能复现扩展后的窗口
+    pub fn extend(&mut self, direction: WindowDirection, slots: u64) {
+        match (self, direction) {
+            (LinkedQuery::Nearby { backward, .. }, WindowDirection::Backward) => {
+                let slots = u32::try_from(slots).unwrap_or(u32::MAX);
+                *backward = Some(backward.unwrap_or(0).saturating_add(slots));
+            }
+            (LinkedQuery::Nearby { forward, .. }, WindowDirection::Forward) => {
+                let slots = u32::try_from(slots).unwrap_or(u32::MAX);
+                *forward = Some(forward.unwrap_or(0).saturating_add(slots));
+            }
+            (
+                LinkedQuery::Block {
+                    start_slot,
+                    end_slot,
+                },
+                WindowDirection::Backward,
+            ) => {
+                // 未指定结束slot时只查询了起始slot，向前扩展后需要保留原来的结束位置
+                end_slot.get_or_insert(*start_slot);
+                *start_slot = start_slot.saturating_sub(slots);
+            }
+            (
+                LinkedQuery::Block {
+                    start_slot,
+                    end_slot,
+                },
+                WindowDirection::Forward,
+            ) => {
+                *end_slot = Some(end_slot.unwrap_or(*start_slot).saturating_add(slots));
+            }
+            (LinkedQuery::Transaction { .. } | LinkedQuery::AddressHistory(_), _) => {}
+        }
+    }
+}
+
+/// 工作空间的分享链接，编码了查询、筛选条件及正在检视的交易，
+/// 形如: `/workspace?module=nearby&sig=…&back=…&fwd=…&filters=…&sel=…`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WorkspaceLink {
+    pub query: Option<LinkedQuery>,
+    pub filters: Option<FilterGroupConfig>,
+    /// 正在检视的交易签名
+    pub selected: Option<String>,
+}
+
+impl WorkspaceLink {
+    pub fn is_empty(&self) -> bool {
+        self.query.is_none() && self.filters.is_none() && self.selected.is_none()
+    }
+
+    /// 编码为查询字符串(不含`?`)
+    pub fn to_query_string(&self) -> String {
+        let mut params: Vec<(&str, String)> = Vec::new();
+        match &self.query {
+            None => {}
+            Some(LinkedQuery::Transaction { signature }) => {
+                params.push(("module", "query".into()));
+                params.push(("sig", signature.clone()));
+            }
+            Some(LinkedQuery::Nearby {
+                signature,
+                backward,
+                forward,
+            }) => {
+                params.push(("module", "nearby".into()));
+                params.push(("sig", signature.clone()));
+                if let Some(backward) = backward {
+                    params.push(("back", backward.to_string()));
+                }
+                if let Some(forward) = forward {
+                    params.push(("fwd", forward.to_string()));
+                }
+            }
+            Some(LinkedQuery::Block {
+                start_slot,
+                end_slot,
+            }) => {
+                params.push(("module", "block".into()));
+                params.push(("start", start_slot.to_string()));
+                if let Some(end_slot) = end_slot {
+                    params.push(("end", end_slot.to_string()));
+                }
+            }
+            Some(LinkedQuery::AddressHistory(query)) => {
+                params.push(("module", "address".into()));
+                params.push(("addr", query.address.clone()));
+                if let Some(before) = &query.before {
+                    params.push(("before", before.clone()));
+                }
+                if let Some(until) = &query.until {
+                    params.push(("until", until.clone()));
+                }
+                params.push(("limit", query.limit.to_string()));
+            }
+        }
+        if let Some(filters) = self.filters.as_ref().filter(|f| !f.is_empty()) {
+            match serde_json::to_string(filters) {
+                Ok(filters) => params.push(("filters", filters)),
+                Err(e) => warn!("序列化筛选条件失败: {e}"),
+            }
+        }
+        if let Some(selected) = &self.selected {
+            params.push(("sel", selected.clone()));
+        }
+        params
+            .iter()
+            .map(|(k, v)| format!("{k}={}", percent_encode(v)))
+            .collect::<Vec<_>>()
+            .join("&")
+    }
+
+    /// 从查询字符串解析，格式错误的参数会被忽略
+    pub fn parse(query: &str) -> Self {
+        let query = query.strip_prefix('?').unwrap_or(query);
+        let params = query
+            .split('&')
+            .filter(|p| !p.is_empty())
+            .map(|p| match p.split_once('=') {
+                Some((k, v)) => (k, percent_decode(v)),
+                None => (p, String::new()),
+            })
+            .collect::<Vec<_>>();
+        let get = |key: &str| {
+            params
+                .iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, v)| v.clone())
+                .filter(|v| !v.is_empty())
+        };
+        let number = |key: &str| {
+            get(key).and_then(|v| match v.parse::<u64>() {
+                Ok(n) => Some(n),
+                Err(e) => {
+                    warn!("链接参数{key}: {v} 格式错误: {e}");
+                    None
+                }
+            })
+        };
+        let number_u32 = |key: &str| number(key).map(|n| u32::try_from(n).unwrap_or(u32::MAX));
+
+        let query = match get("module").as_deref() {
+            Some("query") => get("sig").map(|signature| LinkedQuery::Transaction { signature }),
+            Some("nearby") => get("sig").map(|signature| LinkedQuery::Nearby {
+                signature,
+                backward: number_u32("back"),
+                forward: number_u32("fwd"),
+            }),
+            Some("block") => number("start").map(|start_slot| LinkedQuery::Block {
+                start_slot,
+                end_slot: number("end"),
+            }),
+            Some("address") => get("addr").map(|address| {
+                LinkedQuery::AddressHistory(AddressHistoryQuery {
+                    address,
+                    before: get("before"),
+                    until: get("until"),
+                    limit: number_u32("limit").unwrap_or(DEFAULT_ADDRESS_HISTORY_LIMIT),
+                })
+            }),
+            Some(other) => {
+                warn!("未知的链接模块: {other}");
+                None
+            }
+            None => None,
+        };
+        let filters = get("filters").and_then(|f| match serde_json::from_str(&f) {
+            Ok(filters) => Some(filters),
+            Err(e) => {
+                warn!("链接中的筛选条件格式错误: {e}");
+                None
+            }
+        });
+
+        WorkspaceLink {
+            query,
+            filters,
+            selected: get("sel"),
+        }
+    }
+}
+
+/// 供路由使用
+impl Display for WorkspaceLink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_query_string())
+    }
+}
+
+/// 供路由使用
+impl From<&str> for WorkspaceLink {
+    fn from(query: &str) -> Self {
+        Self::parse(query)
+    }
+}
+
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 3;
+                        continue;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            b'+' => decoded.push(b' '),
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::transaction_filter_config::{FilterItemConfig, FilterItemSpec};
+    use crate::workspace::transaction_filter_expression::{
+        FilterGroupOperator, TransactionFilterId,
+    };
+
+    fn round_trip(link: &WorkspaceLink) -> WorkspaceLink {
+        WorkspaceLink::parse(&link.to_query_string())
+    }
+
+    #[test]
+    fn percent_codec_round_trips() {
+        for value in ["abc-_.~", "a b&c=d", "{\"k\":[1,2]}", "中文 %41", ""] {
+            assert_eq!(percent_decode(&percent_encode(value)), value);
+        }
+        assert_eq!(percent_encode("a b"), "a%20b");
+        assert_eq!(percent_decode("a+b%2Bc"), "a b+c");
+    }
+
+    #[test]
+    fn percent_decode_keeps_malformed_escapes() {
+        assert_eq!(percent_decode("%"), "%");
+        assert_eq!(percent_decode("%4"), "%4");
+        assert_eq!(percent_decode("%zz1"), "%zz1");
+        assert_eq!(percent_decode("%41"), "A");
+    }
+
+    #[test]
+    fn queries_round_trip() {
+        let queries = [
+            LinkedQuery::Transaction {
+                signature: "sig".to_string(),
+            },
+            LinkedQuery::Nearby {
+                signature: "sig".to_string(),
+                backward: Some(3),
+                forward: None,
+            },
+            LinkedQuery::Block {
+                start_slot: 100,
+                end_slot: Some(105),
+            },
+            LinkedQuery::AddressHistory(AddressHistoryQuery {
+                address: "addr".to_string(),
+                before: Some("older".to_string()),
+                until: None,
+                limit: 20,
+            }),
+        ];
+        for query in queries {
+            let link = WorkspaceLink {
+                query: Some(query),
+                filters: None,
+                selected: Some("selected".to_string()),
+            };
+            assert_eq!(round_trip(&link), link);
+        }
+    }
+
+    #[test]
+    fn filters_round_trip_without_ids() {
+        let spec = FilterItemSpec::LogMessage {
+            content: "a&b=c".to_string(),
+            use_regex: false,
+            program_id: None,
+        };
+        let filters = FilterGroupConfig {
+            operator: FilterGroupOperator::Or,
+            negated: true,
+            items: vec![FilterItemConfig::new(
+                TransactionFilterId::next(),
+                true,
+                spec.clone(),
+            )],
+            children: Vec::new(),
+        };
+        let link = WorkspaceLink {
+            query: None,
+            filters: Some(filters),
+            selected: None,
+        };
+        let parsed = round_trip(&link).filters.unwrap();
+        assert_eq!(parsed.operator, FilterGroupOperator::Or);
+        assert!(parsed.negated);
+        assert_eq!(parsed.items.len(), 1);
+        assert!(parsed.items[0].exclude);
+        assert_eq!(parsed.items[0].spec, spec);
+    }
+
+    #[test]
+    fn ignores_malformed_params() {
+        let link = WorkspaceLink::parse("?module=block&start=abc&filters=%7Bbad&sel=");
+        assert!(link.is_empty());
+        let link = WorkspaceLink::parse("module=unknown&sel=x");
+        assert_eq!(link.query, None);
+        assert_eq!(link.selected.as_deref(), Some("x"));
+    }
+
+    #[test]
+    fn extend_updates_window() {
+        let mut query = LinkedQuery::Nearby {
+            signature: "sig".to_string(),
+            backward: None,
+            forward: Some(1),
+        };
+        query.extend(WindowDirection::Backward, 10);
+        query.extend(WindowDirection::Forward, 10);
+        assert_eq!(
+            query,
+            LinkedQuery::Nearby {
+                signature: "sig".to_string(),
+                backward: Some(10),
+                forward: Some(11),
+            }
+        );
+        let mut query = LinkedQuery::Block {
+            start_slot: 5,
+            end_slot: None,
+        };
+        query.extend(WindowDirection::Backward, 10);
+        query.extend(WindowDirection::Forward, 10);
+        assert_eq!(
+            query,
+            LinkedQuery::Block {
+                start_slot: 0,
+                end_slot: Some(15),
+            }
+        );
+    }
+}
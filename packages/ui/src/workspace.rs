@@ -7,4 +7,7 @@ mod left_panel;
 mod block_transaction_component;
 mod block_transaction_list;
 mod transaction_filter_panel;
-mod block_transaction_list_state;
\ No newline at end of file
+mod block_transaction_list_state;
+mod transaction_aggregation_panel;
+mod cached_slots_browser;
+mod transaction_import_panel;
\ No newline at end of file
@@ -1,34 +1,120 @@
 use crate::workspace::block_transaction_list_state::BlockTransactionListState;
 use dioxus::prelude::*;
-use models::workspace::cheap_block_transaction::CheapBlockTransaction;
-use models::workspace::transaction_service::ParsedEncodedConfirmedTransactionWithStatusMeta;
-use solana_transaction_status_client_types::EncodedTransaction;
+use models::WorkspaceState;
+use models::workspace::cheap_block_transaction::FilteredBlockTransaction;
+use models::workspace::cheap_block_transaction::ParsedVersionedTransactionWithStatusMeta;
+use models::workspace::transaction_sort::{
+    SortDirection, TransactionSort, TransactionSortKey, TransactionSortScope,
+};
 use std::rc::Rc;
 
 const STYLE: Asset = asset!("/assets/styling/workspace/block_transaction_component.css");
 #[component]
-pub fn BlockTransactionList(data: Rc<Vec<CheapBlockTransaction>>) -> Element {
+pub fn BlockTransactionList(data: Rc<Vec<FilteredBlockTransaction>>) -> Element {
     let focus = use_signal(|| None);
     use_context_provider(|| BlockTransactionListState { focus_slot: focus });
+    let workspace = use_context::<WorkspaceState>();
+    let sort = *workspace.transaction_service_state.transaction_sort.read();
+    let content = match sort.scope {
+        TransactionSortScope::WithinBlock => rsx! {
+            for block in data.iter() {
+                div {
+                    key: "{block.slot().to_string()}",
+                    BlockTransactionComponent {
+                        data: block.clone(),
+                    }
+                }
+            }
+        },
+        TransactionSortScope::AcrossBlocks => {
+            let positions = sort.flatten(&data);
+            rsx! {
+                for position in positions {
+                    Transaction {
+                        key: "{data[position.block].slot()}_{position.index}",
+                        index: position.index,
+                        transaction: data[position.block].block.transactions[position.index].clone(),
+                    }
+                }
+            }
+        }
+    };
     rsx! {
-        for block in data.iter() {
-            div {
-                key: "{block.slot.to_string()}",
-                BlockTransactionComponent {
-                    data: block.clone(),
+        document::Stylesheet{href: STYLE}
+        TransactionSortBar {}
+        {content}
+    }
+}
+
+#[component]
+fn TransactionSortBar() -> Element {
+    let workspace = use_context::<WorkspaceState>();
+    let mut transaction_service_state = workspace.transaction_service_state;
+    let sort = *transaction_service_state.transaction_sort.read();
+    let on_key_change = move |e: Event<FormData>| {
+        let key = TransactionSortKey::ALL
+            .into_iter()
+            .find(|k| k.to_string() == e.value());
+        if let Some(key) = key {
+            transaction_service_state.set_sort(TransactionSort { key, ..sort });
+        }
+    };
+    let on_click_direction = move |_| {
+        let direction = match sort.direction {
+            SortDirection::Ascending => SortDirection::Descending,
+            SortDirection::Descending => SortDirection::Ascending,
+        };
+        transaction_service_state.set_sort(TransactionSort { direction, ..sort });
+    };
+    let on_click_scope = move |_| {
+        let scope = match sort.scope {
+            TransactionSortScope::WithinBlock => TransactionSortScope::AcrossBlocks,
+            TransactionSortScope::AcrossBlocks => TransactionSortScope::WithinBlock,
+        };
+        transaction_service_state.set_sort(TransactionSort { scope, ..sort });
+    };
+    let direction_label = match sort.direction {
+        SortDirection::Ascending => "升序",
+        SortDirection::Descending => "降序",
+    };
+    let scope_label = match sort.scope {
+        TransactionSortScope::WithinBlock => "按区块分组",
+        TransactionSortScope::AcrossBlocks => "跨区块展开",
+    };
+
+    rsx! {
+        div {
+            class: "transaction_sort_bar",
+            label { "排序: " }
+            select {
+                onchange: on_key_change,
+                for key in TransactionSortKey::ALL {
+                    option {
+                        value: "{key}",
+                        selected: key == sort.key,
+                        "{key}"
+                    }
                 }
             }
+            button {
+                onclick: on_click_direction,
+                {direction_label}
+            }
+            button {
+                onclick: on_click_scope,
+                {scope_label}
+            }
         }
     }
 }
 
 #[component]
-pub fn BlockTransactionComponent(data: CheapBlockTransaction) -> Element {
+pub fn BlockTransactionComponent(data: FilteredBlockTransaction) -> Element {
     let mut state = use_context::<BlockTransactionListState>();
-    let need_show_children = state.focus_slot.read_unchecked().unwrap_or(0) == data.slot;
-    let block_time = data.block_time.unwrap_or(0);
-    let block_height = data.block_height.unwrap_or(0);
-    let my_slot = data.slot;
+    let need_show_children = state.focus_slot.read_unchecked().unwrap_or(0) == data.slot();
+    let block_time = data.block.block_time.unwrap_or(0);
+    let block_height = data.block.block_height.unwrap_or(0);
+    let my_slot = data.slot();
     let on_click = move |_| {
         if *state.focus_slot.peek_unchecked() == Some(my_slot) {
             state.focus_slot.set(None);
@@ -44,7 +130,7 @@ pub fn BlockTransactionComponent(data: CheapBlockTransaction) -> Element {
             div {
                 id: "block_transaction_component_container",
                 label {
-                    "slot: {data.slot}"
+                    "slot: {my_slot}"
                 }
                 label {
                     "区块时间: {block_time}"
@@ -53,13 +139,13 @@ pub fn BlockTransactionComponent(data: CheapBlockTransaction) -> Element {
                     "区块高度: {block_height}"
                 }
                 label {
-                    "交易数: {data.transactions.len()}"
+                    "交易数: {data.len()} / {data.block.transactions.len()}"
                 }
             }
 
             if need_show_children {
                 div {
-                    TransactionList{transactions: data.transactions.clone()}
+                    TransactionList{block: data.clone()}
                 }
             }
         }
@@ -67,24 +153,34 @@ pub fn BlockTransactionComponent(data: CheapBlockTransaction) -> Element {
 }
 
 #[component]
-fn TransactionList(
-    transactions: Rc<Vec<ParsedEncodedConfirmedTransactionWithStatusMeta>>,
-) -> Element {
+fn TransactionList(block: FilteredBlockTransaction) -> Element {
     rsx! {
-        for tx in &*transactions {
-            Transaction {transaction: tx.clone()}
+        for (index, tx) in block.iter() {
+            Transaction {
+                key: "{index}",
+                index,
+                transaction: tx.clone(),
+            }
         }
     }
 }
 
 #[component]
-fn Transaction(transaction: ParsedEncodedConfirmedTransactionWithStatusMeta) -> Element {
-    let real_transaction = &transaction.transaction.transaction;
-    let sig = match &real_transaction.transaction {
-        EncodedTransaction::Json(json) => &json.signatures[0],
-        _ => return rsx! {"交易格式错误(只支持Json)"},
+fn Transaction(index: usize, transaction: ParsedVersionedTransactionWithStatusMeta) -> Element {
+    let sig = match transaction.signature() {
+        Some(sig) => sig,
+        None => return rsx! {"交易格式错误(无法解码交易签名)"},
+    };
+    let workspace = use_context::<WorkspaceState>();
+    let mut transaction_service_state = workspace.transaction_service_state;
+    let on_click_details = {
+        let transaction = transaction.clone();
+        move |_| transaction_service_state.inspect_transaction(transaction.clone())
+    };
+    let on_click_compare = {
+        let transaction = transaction.clone();
+        move |_| transaction_service_state.compare_with_inspecting(transaction.clone())
     };
-    let on_click_details = |_| {};
     let nav = navigator();
     // let on_click_solscan = |e| {
     //     nav.push(format!("https://solscan.io/tx/{sig}"));
@@ -93,6 +189,10 @@ fn Transaction(transaction: ParsedEncodedConfirmedTransactionWithStatusMeta) ->
         div {
             class: "sub_tx_container",
             onclick: |e|e.stop_propagation(),
+            label {
+                class: "sub_tx_position",
+                "slot: {transaction.slot()} #{index}"
+            }
             label {
                 class: "ellipsis-label",
                 "签名: {sig}"
@@ -103,6 +203,11 @@ fn Transaction(transaction: ParsedEncodedConfirmedTransactionWithStatusMeta) ->
                     onclick: on_click_details,
                     "详情"
                 }
+                button {
+                    title: "与正在检视的交易并排对比",
+                    onclick: on_click_compare,
+                    "对比"
+                }
                 Link{
                     class: "solscan_link",
                     new_tab: true,
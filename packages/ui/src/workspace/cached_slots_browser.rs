@@ -0,0 +1,99 @@
+use dioxus::prelude::*;
+use models::WorkspaceState;
+use models::workspace::persistent_block_cache::group_cached_slots;
+use utils::time::now_timestamp_ms;
+
+const STYLE: Asset = asset!("/assets/styling/workspace/cached_slots_browser.css");
+
+/// 相邻的已缓存slot间隔不超过该值时视为同一次调查
+const MAX_SLOT_GAP: u64 = 8;
+
+/// 浏览设备上缓存的区块，可在不访问服务端的情况下重新打开过往的调查
+#[component]
+pub fn CachedSlotsBrowser() -> Element {
+    let workspace = use_context::<WorkspaceState>();
+    let persistent = workspace.transaction_service_state.persistent_block_cache;
+    let mut refresh = use_signal(|| 0u32);
+    let slots = use_resource(move || async move {
+        let _ = refresh();
+        let cache = persistent.read().clone();
+        match cache {
+            Some(cache) => Some(cache.cached_slots().await),
+            None => None,
+        }
+    });
+
+    let content = match &*slots.read_unchecked() {
+        None => rsx! { "正在读取本地缓存..." },
+        Some(None) => rsx! { "本地缓存不可用" },
+        Some(Some(slots)) if slots.is_empty() => rsx! { "没有缓存的区块" },
+        Some(Some(slots)) => {
+            let total_size = slots.iter().map(|s| s.size).sum::<usize>();
+            let megabytes = total_size as f64 / (1024.0 * 1024.0);
+            let now = now_timestamp_ms();
+            let ranges = group_cached_slots(slots, MAX_SLOT_GAP)
+                .into_iter()
+                .map(|(start, end, count)| {
+                    let saved_at = slots
+                        .iter()
+                        .filter(|s| s.slot >= start && s.slot <= end)
+                        .map(|s| s.saved_at_ms)
+                        .max()
+                        .unwrap_or(now);
+                    let hours = (now - saved_at).max(0) / (60 * 60 * 1000);
+                    (start, end, count, hours)
+                })
+                .collect::<Vec<_>>();
+            rsx! {
+                div {
+                    class: "cached_slots_summary",
+                    "共 {slots.len()} 个区块, 约 {megabytes:.1} MB"
+                }
+                for (start, end, count, hours) in ranges.into_iter().rev() {
+                    div {
+                        key: "{start}",
+                        class: "cached_slots_range",
+                        label {
+                            "slot {start} - {end}, {count}个区块, {hours}小时前"
+                        }
+                        button {
+                            onclick: move |_| workspace.open_cached_slots(start, end),
+                            "打开"
+                        }
+                        button {
+                            onclick: move |_| {
+                                let cache = persistent.read().clone();
+                                let Some(cache) = cache else {
+                                    return;
+                                };
+                                spawn(async move {
+                                    for slot in start..=end {
+                                        cache.remove(slot).await;
+                                    }
+                                    refresh += 1;
+                                });
+                            },
+                            "删除"
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    rsx! {
+        document::Stylesheet{href: STYLE}
+        div {
+            id: "cached_slots_browser_container",
+            div {
+                class: "cached_slots_header",
+                label { "本地缓存的区块" }
+                button {
+                    onclick: move |_| refresh += 1,
+                    "刷新"
+                }
+            }
+            {content}
+        }
+    }
+}
@@ -1,7 +1,14 @@
 use crate::workspace::block_transaction_list::BlockTransactionList;
+use crate::workspace::transaction_aggregation_panel::TransactionAggregationPanel;
 use dioxus::prelude::*;
 use models::WorkspaceState;
-use models::workspace::transaction_service::HandlingData;
+use models::workspace::transaction_export::{export_csv, export_json};
+use models::workspace::transaction_service::{
+    EXTEND_WINDOW_SLOTS, FilteredHandlingData, TransactionServiceModule, WindowDirection,
+};
+use utils::file_save::save_text_file;
+
+const STYLE: Asset = asset!("/assets/styling/workspace/block_transaction_component.css");
 
 #[component]
 pub fn LeftPanel() -> Element {
@@ -21,21 +28,142 @@ fn route_content() -> Element {
             }
         }
         Some(resp) => match resp {
-            HandlingData::Query(_) => {
+            FilteredHandlingData::Query(_) => {
                 rsx! {
                     div {
                         style: "height: 100%; display: flex; flex-direction: column;justify-content: center;",
+                        ExportBar {}
                         div {
                             "无额外数据"
                         }
                     }
                 }
             }
-            HandlingData::QueryNearby(all) => {
+            FilteredHandlingData::QueryNearby(all) => {
+                let statistics = transaction_service.transaction_filter_statistics.read();
                 rsx! {
-                    BlockTransactionList {data: all.clone()}
+                    div {
+                        class: "filter_statistics_summary",
+                        "保留 {statistics.kept} / {statistics.total} 笔交易"
+                    }
+                    WindowExtensionBar {}
+                    ExportBar {}
+                    div {
+                        class: "transaction_list_with_aggregation",
+                        div {
+                            class: "transaction_list_column",
+                            BlockTransactionList {data: all.clone()}
+                        }
+                        TransactionAggregationPanel {data: all.clone()}
+                    }
                 }
             }
         },
     }
 }
+
+/// 在当前区块列表前后追加加载slot，地址历史的交易不连续，不支持扩展
+#[component]
+fn WindowExtensionBar() -> Element {
+    let workspace = use_context::<WorkspaceState>();
+    let focus = *workspace
+        .transaction_service_state
+        .transaction_focus
+        .read();
+    if focus == TransactionServiceModule::AddressHistory {
+        return rsx! {};
+    }
+    let disabled = workspace.transaction_service_state.is_processing();
+    rsx! {
+        div {
+            class: "window_extension_bar",
+            button {
+                disabled: disabled,
+                onclick: move |_| {
+                    workspace.extend_window(WindowDirection::Backward, EXTEND_WINDOW_SLOTS)
+                },
+                "向前加载{EXTEND_WINDOW_SLOTS}个slot"
+            }
+            button {
+                disabled: disabled,
+                onclick: move |_| {
+                    workspace.extend_window(WindowDirection::Forward, EXTEND_WINDOW_SLOTS)
+                },
+                "向后加载{EXTEND_WINDOW_SLOTS}个slot"
+            }
+        }
+    }
+}
+
+
+#[derive(Copy, Clone, PartialEq)]
+enum ExportFormat {
+    Csv,
+    Json,
+}
+
+/// 将当前通过过滤的交易按列表顺序导出
+#[component]
+fn ExportBar() -> Element {
+    let workspace = use_context::<WorkspaceState>();
+    let mut export_error = use_signal(|| None::<String>);
+    let mut export = move |format: ExportFormat| {
+        let transaction_service = &workspace.transaction_service_state;
+        let Some(data) = transaction_service.filtered_handling_data.peek().clone() else {
+            return;
+        };
+        let sort = *transaction_service.transaction_sort.peek();
+        let (content, extension, mime) = match format {
+            ExportFormat::Csv => (export_csv(&data, &sort), "csv", "text/csv"),
+            ExportFormat::Json => match export_json(&data, &sort) {
+                Ok(content) => (content, "json", "application/json"),
+                Err(e) => {
+                    export_error.set(Some(format!("导出失败: {e}")));
+                    return;
+                }
+            },
+        };
+        let file_name = format!("transactions_{}.{extension}", export_name(&data));
+        spawn(async move {
+            match save_text_file(&file_name, mime, &content).await {
+                Ok(_) => export_error.set(None),
+                Err(e) => export_error.set(Some(format!("导出失败: {e}"))),
+            }
+        });
+    };
+    rsx! {
+        document::Stylesheet{href: STYLE}
+        div {
+            class: "export_bar",
+            button {
+                onclick: move |_| export(ExportFormat::Csv),
+                "导出CSV"
+            }
+            button {
+                onclick: move |_| export(ExportFormat::Json),
+                "导出JSON"
+            }
+            if let Some(error) = &*export_error.read() {
+                span {
+                    class: "export_error",
+                    "{error}"
+                }
+            }
+        }
+    }
+}
+
+/// 导出文件名: 单笔交易使用签名，区块列表使用slot范围
+fn export_name(data: &FilteredHandlingData) -> String {
+    match data {
+        FilteredHandlingData::Query(tx) => tx
+            .signature()
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| tx.slot().to_string()),
+        FilteredHandlingData::QueryNearby(blocks) => {
+            let start = blocks.iter().map(|b| b.slot()).min().unwrap_or_default();
+            let end = blocks.iter().map(|b| b.slot()).max().unwrap_or_default();
+            format!("{start}-{end}")
+        }
+    }
+}
\ No newline at end of file
@@ -1,7 +1,10 @@
 mod content;
 mod transaction_details_page;
+mod transaction_comparison_page;
 mod signature_component;
 mod block_component;
+mod balance_change_table;
+mod log_tree_view;
 
 use crate::workspace::main::content::Content;
 use dioxus::prelude::*;
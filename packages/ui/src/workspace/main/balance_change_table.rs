@@ -0,0 +1,133 @@
+use dioxus::prelude::*;
+use models::workspace::balance_change::{
+    format_lamports, format_token_amount, largest_sol_changes, sol_balance_changes,
+};
+use models::workspace::cheap_block_transaction::ParsedVersionedTransactionWithStatusMeta;
+
+const STYLE: Asset = asset!("/assets/styling/workspace/balance_change_table.css");
+/// 高亮余额增加/减少最多的帐号数
+const HIGHLIGHT_N: usize = 3;
+
+/// 交易中每个帐号的SOL余额变化，第一个帐号为手续费支付者
+#[component]
+pub fn SolBalanceTable(data: ParsedVersionedTransactionWithStatusMeta) -> Element {
+    let changes = sol_balance_changes(&data);
+    if changes.is_empty() {
+        return rsx! {
+            div { "没有余额数据" }
+        };
+    }
+    let (gainers, losers) = largest_sol_changes(&changes, HIGHLIGHT_N);
+
+    rsx! {
+        document::Stylesheet{href: STYLE}
+        table {
+            class: "balance_table",
+            caption { "SOL余额变化" }
+            thead {
+                tr {
+                    th { "#" }
+                    th { "帐号" }
+                    th { "权限" }
+                    th { "交易前(SOL)" }
+                    th { "交易后(SOL)" }
+                    th { "变化(SOL)" }
+                }
+            }
+            tbody {
+                for (i, change) in changes.iter().enumerate() {
+                    {
+                        let delta = change.delta();
+                        let mut class = Vec::new();
+                        if i == 0 {
+                            class.push("balance_fee_payer");
+                        }
+                        if gainers.contains(&i) {
+                            class.push("balance_gainer");
+                        } else if losers.contains(&i) {
+                            class.push("balance_loser");
+                        }
+                        let class = class.join(" ");
+                        let mut flags = Vec::new();
+                        if i == 0 {
+                            flags.push("手续费支付者");
+                        }
+                        if change.account.signer {
+                            flags.push("签名");
+                        }
+                        if change.account.writable {
+                            flags.push("可写");
+                        }
+                        let flags = flags.join("/");
+                        let sign = if delta > 0 { "+" } else { "" };
+                        let delta = format!("{sign}{}", format_lamports(delta));
+                        rsx! {
+                            tr {
+                                key: "{i}",
+                                class: "{class}",
+                                td { "{i}" }
+                                td { class: "balance_account", "{change.account.pubkey}" }
+                                td { "{flags}" }
+                                td { {format_lamports(change.pre as i128)} }
+                                td { {format_lamports(change.post as i128)} }
+                                td { "{delta}" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// 交易的代币余额变化，按持有者及mint合并，数量按精度换算
+#[component]
+pub fn TokenBalanceTable(data: ParsedVersionedTransactionWithStatusMeta) -> Element {
+    let changes = data.token_balance_changes();
+    if changes.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        document::Stylesheet{href: STYLE}
+        table {
+            class: "balance_table",
+            caption { "代币余额变化" }
+            thead {
+                tr {
+                    th { "持有者" }
+                    th { "mint" }
+                    th { "交易前" }
+                    th { "交易后" }
+                    th { "变化" }
+                }
+            }
+            tbody {
+                for (i, change) in changes.iter().enumerate() {
+                    {
+                        let delta = change.delta();
+                        let class = match delta.signum() {
+                            1 => "balance_gainer",
+                            -1 => "balance_loser",
+                            _ => "",
+                        };
+                        let sign = if delta > 0 { "+" } else { "" };
+                        let delta = format!("{sign}{}", format_token_amount(delta, change.decimals));
+                        let owner = change.owner.clone().unwrap_or("-".to_string());
+                        rsx! {
+                            tr {
+                                key: "{i}",
+                                class: "{class}",
+                                td { class: "balance_account", "{owner}" }
+                                td { class: "balance_account", "{change.mint}" }
+                                td { {format_token_amount(change.pre as i128, change.decimals)} }
+                                td { {format_token_amount(change.post as i128, change.decimals)} }
+                                td { "{delta}" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
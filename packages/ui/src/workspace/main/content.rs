@@ -1,3 +1,4 @@
+use crate::workspace::main::transaction_comparison_page::TransactionComparisonPage;
 use crate::workspace::main::transaction_details_page::TransactionDetailsPage;
 use dioxus::hooks::{use_context};
 use dioxus::prelude::*;
@@ -31,6 +32,11 @@ fn route_content(inspecting_data: &Option<InspectingDataStatus>) -> Element {
                     TransactionDetailsPage {data: data.clone()}
                 }
             }
+            InspectingData::Comparison { left, right } => {
+                rsx! {
+                    TransactionComparisonPage {left: left.clone(), right: right.clone()}
+                }
+            }
         },
     }
 }
@@ -0,0 +1,106 @@
+use dioxus::prelude::*;
+use models::workspace::cheap_block_transaction::ParsedVersionedTransactionWithStatusMeta;
+use models::workspace::log_tree::{InvocationFrame, InvocationResult, LogEntry, parse_log_tree};
+
+const STYLE: Asset = asset!("/assets/styling/workspace/log_tree_view.css");
+
+/// 按程序调用树展示交易日志，每次调用可以折叠
+#[component]
+pub fn LogTreeView(data: ParsedVersionedTransactionWithStatusMeta) -> Element {
+    let logs = data
+        .meta()
+        .and_then(|m| Option::<&Vec<String>>::from(m.log_messages.as_ref()))
+        .cloned()
+        .unwrap_or_default();
+    if logs.is_empty() {
+        return rsx! {
+            div { "没有日志" }
+        };
+    }
+    let entries = parse_log_tree(&logs);
+
+    rsx! {
+        document::Stylesheet{href: STYLE}
+        div {
+            class: "log_tree",
+            div { class: "log_tree_title", "日志" }
+            for entry in entries {
+                LogEntryView { entry }
+            }
+        }
+    }
+}
+
+#[component]
+fn LogEntryView(entry: LogEntry) -> Element {
+    match entry {
+        LogEntry::Invocation(frame) => rsx! {
+            InvocationFrameView { frame }
+        },
+        LogEntry::Log(message) => rsx! {
+            div { class: "log_line log_message", "log: {message}" }
+        },
+        LogEntry::Data(chunks) => rsx! {
+            div {
+                class: "log_line log_data",
+                for (base64, hex) in chunks {
+                    div {
+                        div { class: "log_data_raw", "data: {base64}" }
+                        div {
+                            class: "log_data_hex",
+                            {hex.unwrap_or("(无法解码)".to_string())}
+                        }
+                    }
+                }
+            }
+        },
+        LogEntry::Other(log) => rsx! {
+            div { class: "log_line", "{log}" }
+        },
+    }
+}
+
+/// 一次程序调用，标题显示程序、深度、CU消耗及结果，点击标题折叠/展开其中的日志
+#[component]
+fn InvocationFrameView(frame: InvocationFrame) -> Element {
+    let mut expanded = use_signal(|| true);
+    let InvocationFrame {
+        program_id,
+        depth,
+        entries,
+        compute_units,
+        result,
+    } = frame;
+    let compute_units = compute_units
+        .map(|(consumed, limit)| format!("消耗 {consumed} / {limit} CU"))
+        .unwrap_or_default();
+    let (result_class, result) = match result {
+        Some(InvocationResult::Success) => ("log_frame_success", "成功".to_string()),
+        Some(InvocationResult::Failed(reason)) => ("log_frame_failed", format!("失败: {reason}")),
+        None => ("log_frame_unknown", "未结束".to_string()),
+    };
+    let arrow = if expanded() { "▼" } else { "▶" };
+
+    rsx! {
+        div {
+            class: "log_frame",
+            div {
+                class: "log_frame_header",
+                onclick: move |_| expanded.set(!expanded()),
+                span { "{arrow} " }
+                span { class: "log_frame_program", "{program_id}" }
+                span { " [{depth}] " }
+                span { class: "log_frame_cu", "{compute_units}" }
+                span { class: "{result_class}", " {result}" }
+            }
+            if expanded() {
+                div {
+                    class: "log_frame_body",
+                    for entry in entries {
+                        LogEntryView { entry }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use dioxus::prelude::*;
+use models::WorkspaceState;
+use models::workspace::balance_change::{format_lamports, format_token_amount};
+use models::workspace::cheap_block_transaction::ParsedVersionedTransactionWithStatusMeta;
+use models::workspace::transaction_comparison::{
+    DiffRow, InvocationStep, TokenComparison, TransactionComparison,
+};
+use models::workspace::transaction_encoding::AccountKey;
+
+const STYLE: Asset = asset!("/assets/styling/workspace/transaction_comparison_page.css");
+
+/// 并排对比两笔交易，差异所在的行被高亮
+#[component]
+pub fn TransactionComparisonPage(
+    left: ParsedVersionedTransactionWithStatusMeta,
+    right: ParsedVersionedTransactionWithStatusMeta,
+) -> Element {
+    let workspace = use_context::<WorkspaceState>();
+    let mut transaction_service_state = workspace.transaction_service_state;
+    let comparison = TransactionComparison::new(&left, &right);
+    let TransactionComparison {
+        accounts,
+        left_steps,
+        right_steps,
+        steps,
+        tokens,
+        left_logs,
+        right_logs,
+        logs,
+    } = comparison;
+    let summary = [
+        ("签名", signature(&left), signature(&right)),
+        ("slot", left.slot().to_string(), right.slot().to_string()),
+        ("结果", status(&left), status(&right)),
+        ("手续费(lamports)", optional(left.fee()), optional(right.fee())),
+        (
+            "消耗CU",
+            optional(left.compute_units_consumed()),
+            optional(right.compute_units_consumed()),
+        ),
+    ];
+    let on_view_left = {
+        let left = left.clone();
+        move |_| transaction_service_state.inspect_transaction(left.clone())
+    };
+    let on_view_right = {
+        let right = right.clone();
+        move |_| transaction_service_state.inspect_transaction(right.clone())
+    };
+
+    rsx! {
+        document::Stylesheet{href: STYLE}
+        div {
+            id: "tx_comparison_container",
+            div {
+                class: "comparison_toolbar",
+                button {
+                    onclick: move |_| transaction_service_state.swap_comparison(),
+                    "交换左右"
+                }
+                button {
+                    onclick: on_view_left,
+                    "只看左侧"
+                }
+                button {
+                    onclick: on_view_right,
+                    "只看右侧"
+                }
+            }
+
+            ComparisonSection {
+                title: "概要",
+                for (name, l, r) in summary {
+                    div {
+                        class: row_class(l != r),
+                        div { class: "comparison_label", "{name}" }
+                        div { class: "comparison_cell", "{l}" }
+                        div { class: "comparison_cell", "{r}" }
+                    }
+                }
+            }
+
+            ComparisonSection {
+                title: "帐号及SOL余额变化",
+                for account in accounts {
+                    div {
+                        class: row_class(account.is_different()),
+                        div { class: "comparison_label ellipsis-label", "{account.pubkey}" }
+                        div { class: "comparison_cell", {account_cell(account.left.as_ref())} }
+                        div { class: "comparison_cell", {account_cell(account.right.as_ref())} }
+                    }
+                }
+            }
+
+            ComparisonSection {
+                title: "指令序列",
+                for row in steps {
+                    {diff_row(row, |i| step_label(&left_steps[i]), |i| step_label(&right_steps[i]))}
+                }
+            }
+
+            ComparisonSection {
+                title: "代币余额变化",
+                if tokens.is_empty() {
+                    div { "无代币余额变化" }
+                }
+                for token in tokens {
+                    {token_row(token)}
+                }
+            }
+
+            ComparisonSection {
+                title: "日志",
+                for row in logs {
+                    {diff_row(row, |i| left_logs[i].clone(), |i| right_logs[i].clone())}
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ComparisonSection(title: String, children: Element) -> Element {
+    let mut expanded = use_signal(|| true);
+    rsx! {
+        div {
+            class: "comparison_section",
+            div {
+                class: "comparison_section_title",
+                onclick: move |_| expanded.set(!expanded()),
+                if expanded() { "▼ {title}" } else { "▶ {title}" }
+            }
+            if expanded() {
+                {children}
+            }
+        }
+    }
+}
+
+fn diff_row(
+    row: DiffRow,
+    left: impl Fn(usize) -> String,
+    right: impl Fn(usize) -> String,
+) -> Element {
+    let (class, l, r) = match row {
+        DiffRow::Same(i, j) => ("comparison_row", left(i), right(j)),
+        DiffRow::Changed(i, j) => ("comparison_row comparison_changed", left(i), right(j)),
+        DiffRow::LeftOnly(i) => ("comparison_row comparison_left_only", left(i), String::new()),
+        DiffRow::RightOnly(j) => ("comparison_row comparison_right_only", String::new(), right(j)),
+    };
+    rsx! {
+        div {
+            class: "{class}",
+            div { class: "comparison_cell comparison_text", "{l}" }
+            div { class: "comparison_cell comparison_text", "{r}" }
+        }
+    }
+}
+
+/// CPI调用按深度缩进
+fn step_label(step: &InvocationStep) -> String {
+    format!("{}{}", "  ".repeat(step.depth), step.program_id)
+}
+
+fn token_row(token: TokenComparison) -> Element {
+    let owner = token.owner.clone().unwrap_or("-".to_string());
+    let amount = |delta: Option<i128>| {
+        delta
+            .map(|d| format_token_amount(d, token.decimals))
+            .unwrap_or("-".to_string())
+    };
+    rsx! {
+        div {
+            class: row_class(token.is_different()),
+            div {
+                class: "comparison_label",
+                div { class: "ellipsis-label", "所有者: {owner}" }
+                div { class: "ellipsis-label", "mint: {token.mint}" }
+            }
+            div { class: "comparison_cell", {amount(token.left)} }
+            div { class: "comparison_cell", {amount(token.right)} }
+        }
+    }
+}
+
+fn account_cell(account: Option<&(AccountKey, i128)>) -> Element {
+    let Some((key, delta)) = account else {
+        return rsx! { "-" };
+    };
+    let mut flags = Vec::new();
+    if key.signer {
+        flags.push("签名");
+    }
+    if key.writable {
+        flags.push("可写");
+    }
+    let flags = if flags.is_empty() { "只读".to_string() } else { flags.join("/") };
+    let delta = format_lamports(*delta);
+    rsx! {
+        "{flags}  {delta} SOL"
+    }
+}
+
+fn row_class(different: bool) -> &'static str {
+    if different {
+        "comparison_row comparison_changed"
+    } else {
+        "comparison_row"
+    }
+}
+
+fn signature(transaction: &ParsedVersionedTransactionWithStatusMeta) -> String {
+    transaction
+        .signature()
+        .map(|s| s.to_string())
+        .unwrap_or("-".to_string())
+}
+
+fn status(transaction: &ParsedVersionedTransactionWithStatusMeta) -> String {
+    match transaction.is_success() {
+        Some(true) => "成功".to_string(),
+        Some(false) => "失败".to_string(),
+        None => "-".to_string(),
+    }
+}
+
+fn optional(value: Option<u64>) -> String {
+    value.map(|v| v.to_string()).unwrap_or("-".to_string())
+}
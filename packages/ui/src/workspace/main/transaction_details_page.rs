@@ -1,30 +1,31 @@
+use crate::workspace::main::balance_change_table::{SolBalanceTable, TokenBalanceTable};
 use crate::workspace::main::block_component::BlockComponent;
+use crate::workspace::main::log_tree_view::LogTreeView;
 use crate::workspace::main::signature_component::SignatureComponent;
 use dioxus::core_macro::rsx;
 use dioxus::prelude::*;
-use solana_transaction_status_client_types::{
-    EncodedConfirmedTransactionWithStatusMeta, UiTransactionStatusMeta,
-};
+use solana_transaction_status_client_types::UiTransactionStatusMeta;
 use block_insight_cross::parsed_instruction::{ParsedInstruction, ParsedInstructionData};
-use models::workspace::transaction_service::ParsedEncodedConfirmedTransactionWithStatusMeta;
+use models::workspace::transaction_encoding::JsonParsedInstructions;
+use models::workspace::cheap_block_transaction::ParsedVersionedTransactionWithStatusMeta;
 
 const STYLE: Asset = asset!("/assets/styling/workspace/transaction_details_page.css");
 #[component]
-pub fn TransactionDetailsPage(data: ParsedEncodedConfirmedTransactionWithStatusMeta) -> Element {
-    let ParsedEncodedConfirmedTransactionWithStatusMeta {
-        transaction,
+pub fn TransactionDetailsPage(data: ParsedVersionedTransactionWithStatusMeta) -> Element {
+    let ParsedVersionedTransactionWithStatusMeta {
         parsed_instructions,
-    } = data;
-    let EncodedConfirmedTransactionWithStatusMeta {
-        slot, block_time, ..
-    } = &*transaction;
-    let result = result(transaction.transaction.meta.as_ref());
+        json_parsed_instructions,
+        ..
+    } = data.clone();
+    let slot = data.slot();
+    let block_time = data.block_time();
+    let result = result(data.meta());
     rsx! {
         document::Stylesheet{href: STYLE}
         div {
             id: "tx_details_container",
-            SignatureComponent{data: transaction.clone()}
-            BlockComponent {slot: *slot, block_time: *block_time}
+            SignatureComponent{data: data.clone()}
+            BlockComponent {slot: slot, block_time: block_time}
             div {
                 style:"text-align: start",
                 label{"结果: "}
@@ -32,9 +33,12 @@ pub fn TransactionDetailsPage(data: ParsedEncodedConfirmedTransactionWithStatusM
                     "{result}"
                 }
             }
+            SolBalanceTable {data: data.clone()}
+            TokenBalanceTable {data: data.clone()}
+            LogTreeView {data: data.clone()}
             div {
-                for ins in &parsed_instructions {
-                    {instruction_view(ins)}
+                for (i, ins) in parsed_instructions.iter().enumerate() {
+                    {instruction_view(ins, i, None, &mut 0, &json_parsed_instructions)}
                 }
             }
             // {content}
@@ -43,27 +47,49 @@ pub fn TransactionDetailsPage(data: ParsedEncodedConfirmedTransactionWithStatusM
 }
 
 const INSTRUCTION_VIEW_STYLE: Asset = asset!("/assets/styling/workspace/instruction_view.css");
-fn instruction_view(instruction: &ParsedInstruction) -> Element {
+/// top_index为所属顶层指令的下标，inner_index为在该顶层指令的内部指令中(按执行顺序)的下标，
+/// inner_counter用于按执行顺序为内部指令编号
+fn instruction_view(
+    instruction: &ParsedInstruction,
+    top_index: usize,
+    inner_index: Option<usize>,
+    inner_counter: &mut usize,
+    json_parsed_instructions: &JsonParsedInstructions,
+) -> Element {
     let program_id = instruction.program_id_index;
-    let program_name = match &instruction.instruction_data {
-        ParsedInstructionData::System(_) => "系统".to_string(),
-        ParsedInstructionData::SplToken(_) => "SPL TOKEN".to_string(),
-        ParsedInstructionData::SplToken2022(_) => "SPL TOKEN 2022".to_string(),
-        ParsedInstructionData::Error(e) => {
-            format!("错误: {e}")
+    // 已被服务端解析的指令没有原始数据，该位置的指令只有程序下标可用，使用服务端的解析结果
+    let program_name = match json_parsed_instructions.get(top_index, inner_index) {
+        Some(parsed) => {
+            format!("{}: {}", parsed.program, parsed.instruction_type().unwrap_or("未知"))
         }
-        ParsedInstructionData::Unknown => "未知指令".to_string(),
+        None => match &instruction.instruction_data {
+            ParsedInstructionData::System(_) => "系统".to_string(),
+            ParsedInstructionData::SplToken(_) => "SPL TOKEN".to_string(),
+            ParsedInstructionData::SplToken2022(_) => "SPL TOKEN 2022".to_string(),
+            ParsedInstructionData::Error(e) => format!("错误: {e}"),
+            ParsedInstructionData::Unknown => "未知指令".to_string(),
+        },
     };
-    let inner = if let Some(inner) = instruction.inner_instructions.as_ref() {
-        rsx!{
-            for inner in inner {
-                div{
-                    {instruction_view(inner)}
-                }
+    let mut inner_views = Vec::new();
+    if let Some(inner) = instruction.inner_instructions.as_ref() {
+        for inner in inner {
+            let index = *inner_counter;
+            *inner_counter += 1;
+            inner_views.push(instruction_view(
+                inner,
+                top_index,
+                Some(index),
+                inner_counter,
+                json_parsed_instructions,
+            ));
+        }
+    }
+    let inner = rsx!{
+        for view in inner_views {
+            div{
+                {view}
             }
         }
-    } else {
-        rsx!{}
     };
     rsx! {
         document::Stylesheet{href: INSTRUCTION_VIEW_STYLE}
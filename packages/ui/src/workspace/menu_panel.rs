@@ -5,6 +5,14 @@ use models::WorkspaceState;
 
 const MENU_PANEL_STYLE: Asset = asset!("/assets/styling/workspace/menu_panel.css");
 
+/// 以当前页面的地址拼接分享链接并复制到剪贴板
+const COPY_SHARE_LINK_SCRIPT: &str = r#"
+    const query = await dioxus.recv();
+    const url = window.location.origin + "/workspace" + (query ? "?" + query : "");
+    await navigator.clipboard.writeText(url);
+    return url;
+"#;
+
 #[derive(Props, PartialEq, Clone)]
 pub struct MenuPanelProps {}
 
@@ -27,6 +35,74 @@ pub fn MenuPanel(props: MenuPanelProps) -> Element {
                 },
                 "查询相邻交易"
             }
+
+            button {
+                onclick: move |_| {
+                    transaction_service_state.focus(TransactionServiceModule::QueryBlock);
+                },
+                "按slot查询区块"
+            }
+
+            button {
+                onclick: move |_| {
+                    transaction_service_state.focus(TransactionServiceModule::AddressHistory);
+                },
+                "地址交易历史"
+            }
+
+            button {
+                onclick: move |_| {
+                    transaction_service_state.focus(TransactionServiceModule::CachedSlots);
+                },
+                "本地缓存"
+            }
+
+            button {
+                onclick: move |_| {
+                    transaction_service_state.focus(TransactionServiceModule::Import);
+                },
+                "导入数据"
+            }
+
+            ShareLinkButton {}
+        }
+    }
+}
+
+/// 复制能复现当前查询、筛选条件及检视交易的链接
+#[component]
+fn ShareLinkButton() -> Element {
+    let workspace = use_context::<WorkspaceState>();
+    let mut copied_link = use_signal(|| None::<Result<String, String>>);
+    let on_click = move |_| async move {
+        let query = workspace.share_link().to_query_string();
+        let mut eval = document::eval(COPY_SHARE_LINK_SCRIPT);
+        let ret = match eval.send(query) {
+            Ok(_) => eval.join::<String>().await.map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        copied_link.set(Some(ret));
+    };
+    rsx! {
+        button {
+            onclick: on_click,
+            "复制分享链接"
+        }
+        match &*copied_link.read() {
+            None => rsx! {},
+            Some(Ok(link)) => rsx! {
+                div {
+                    class: "share_link",
+                    title: "{link}",
+                    "已复制: {link}"
+                }
+            },
+            Some(Err(e)) => rsx! {
+                div {
+                    class: "share_link_error",
+                    "复制失败: {e}"
+                }
+            },
         }
     }
 }
@@ -1,5 +1,7 @@
+use crate::workspace::cached_slots_browser::CachedSlotsBrowser;
 use crate::workspace::menu_panel::MenuPanel;
 use crate::workspace::transaction_filter_panel::TransactionFilterPanel;
+use crate::workspace::transaction_import_panel::TransactionImportPanel;
 use crate::workspace::transaction_info_input_panel::TransactionInfoInputPanel;
 use dioxus::prelude::*;
 use models::WorkspaceState;
@@ -14,7 +16,14 @@ pub fn RightPanel() -> Element {
         .transaction_service_state
         .transaction_focus
         .read_unchecked();
-    let need_filters_panel = (focus == TransactionServiceModule::QueryNearby);
+    let need_filters_panel = matches!(
+        focus,
+        TransactionServiceModule::QueryNearby
+            | TransactionServiceModule::QueryBlock
+            | TransactionServiceModule::AddressHistory
+            | TransactionServiceModule::CachedSlots
+            | TransactionServiceModule::Import
+    );
     rsx! {
         document::Stylesheet{href: RIGHT_PANEL}
         div {
@@ -33,9 +42,21 @@ pub fn RightPanel() -> Element {
                         }
                     }
                 }
-                div {
-                    id: "input_panel",
-                    TransactionInfoInputPanel {
+                if focus == TransactionServiceModule::CachedSlots {
+                    div {
+                        id: "input_panel",
+                        CachedSlotsBrowser {}
+                    }
+                } else if focus == TransactionServiceModule::Import {
+                    div {
+                        id: "input_panel",
+                        TransactionImportPanel {}
+                    }
+                } else {
+                    div {
+                        id: "input_panel",
+                        TransactionInfoInputPanel {
+                        }
                     }
                 }
             }
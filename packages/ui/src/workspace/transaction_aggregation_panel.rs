@@ -0,0 +1,116 @@
+use dioxus::prelude::*;
+use models::workspace::cheap_block_transaction::FilteredBlockTransaction;
+use models::workspace::transaction_aggregation::TransactionAggregation;
+use models::workspace::transaction_filter::range_filter::LAMPORTS_PER_SOL;
+use std::rc::Rc;
+
+const STYLE: Asset = asset!("/assets/styling/workspace/transaction_aggregation_panel.css");
+const TOP_N: usize = 5;
+
+/// 当前可见交易的汇总统计，跟随筛选结果变化
+#[component]
+pub fn TransactionAggregationPanel(data: Rc<Vec<FilteredBlockTransaction>>) -> Element {
+    let mut expanded = use_signal(|| true);
+    let aggregation = TransactionAggregation::from_blocks(&data, TOP_N);
+    let TransactionAggregation {
+        transaction_count,
+        success_count,
+        failure_count,
+        total_fee,
+        median_fee,
+        compute_unit_histogram,
+        transactions_per_slot,
+        top_programs,
+        top_signers,
+    } = aggregation.clone();
+    let success_ratio = aggregation
+        .success_ratio()
+        .map(|r| format!("{:.1}%", r * 100.0))
+        .unwrap_or("-".to_string());
+    let total_fee_sol = total_fee as f64 / LAMPORTS_PER_SOL as f64;
+    let median_fee = median_fee
+        .map(|f| f.to_string())
+        .unwrap_or("-".to_string());
+    let max_bucket = compute_unit_histogram
+        .iter()
+        .map(|b| b.count)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let slot_count = transactions_per_slot.len();
+    let average_per_slot = if slot_count == 0 {
+        0.0
+    } else {
+        transaction_count as f64 / slot_count as f64
+    };
+
+    rsx! {
+        document::Stylesheet{href: STYLE}
+        div {
+            id: "aggregation_panel_container",
+            div {
+                class: "aggregation_panel_title",
+                onclick: move |_| expanded.set(!expanded()),
+                if expanded() { "▼ 统计" } else { "▶ 统计" }
+            }
+            if expanded() {
+                div {
+                    class: "aggregation_section",
+                    div { "交易数: {transaction_count}" }
+                    div { "成功/失败: {success_count} / {failure_count} ({success_ratio})" }
+                    div { "手续费总额: {total_fee} lamports ({total_fee_sol:.6} SOL)" }
+                    div { "手续费中位数: {median_fee} lamports" }
+                    div { "平均每slot交易数: {average_per_slot:.1} ({slot_count}个slot)" }
+                }
+                div {
+                    class: "aggregation_section",
+                    div { "CU分布:" }
+                    for bucket in compute_unit_histogram {
+                        div {
+                            class: "aggregation_histogram_row",
+                            label {
+                                class: "aggregation_histogram_label",
+                                match bucket.end {
+                                    Some(end) => rsx!{"{bucket.start}-{end}"},
+                                    None => rsx!{"≥{bucket.start}"},
+                                }
+                            }
+                            div {
+                                class: "aggregation_histogram_bar",
+                                style: "width: {bucket.count * 100 / max_bucket}%",
+                            }
+                            label { "{bucket.count}" }
+                        }
+                    }
+                }
+                div {
+                    class: "aggregation_section",
+                    div { "每slot交易数:" }
+                    for (slot, count) in transactions_per_slot {
+                        div { "{slot}: {count}" }
+                    }
+                }
+                div {
+                    class: "aggregation_section",
+                    div { "调用最多的程序:" }
+                    for (program, count) in top_programs {
+                        div {
+                            class: "ellipsis-label",
+                            "{count} - {program}"
+                        }
+                    }
+                }
+                div {
+                    class: "aggregation_section",
+                    div { "交易最多的签名者:" }
+                    for (signer, count) in top_signers {
+                        div {
+                            class: "ellipsis-label",
+                            "{count} - {signer}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
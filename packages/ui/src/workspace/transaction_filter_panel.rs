@@ -1,194 +1,525 @@
-use block_insight_cross::transaction::transaction_filter::account_filter::AccountFilter;
-use block_insight_cross::transaction::transaction_filter::circle_swap_filter::CircleSwapFilter;
-use block_insight_cross::transaction::transaction_filter::signature_filter::SignatureFilter;
-use block_insight_cross::transaction::transaction_filter::status_filter::TransactionStatusFilter;
-use block_insight_cross::transaction::transaction_filter::{
-    TransactionFilter, TransactionFilterContext,
-};
 use dioxus::core_macro::rsx;
 use dioxus::logger::tracing::info;
 use dioxus::prelude::*;
 use models::WorkspaceState;
-use std::any::TypeId;
-use std::collections::HashMap;
+use models::workspace::transaction_filter::log_message_filter::LogMessageFilter;
+use models::workspace::transaction_filter::range_filter::parse_sol_to_lamports;
+use models::workspace::transaction_filter::token_balance_filter::TokenBalanceDirection;
+use models::workspace::transaction_filter_config::{
+    FilterGroupConfig, FilterItemConfig, FilterItemSpec,
+};
+use models::workspace::transaction_filter_expression::{FilterGroupOperator, TransactionFilterId};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 const FILTER_PANEL_STYLE: Asset = asset!("/assets/styling/workspace/transaction_filter_panel.css");
 
-#[derive(Clone, Copy, PartialEq)]
-struct FilterContext {
-    need_filter_account: Signal<bool>,
-    filtering_accounts: Signal<String>,
+/// 筛选项的种类
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum FilterItemKind {
+    Signature,
+    Account,
+    Status,
+    CircleSwap,
+    ProgramId,
+    Fee,
+    ComputeUnits,
+    LogMessage,
+    TokenBalance,
+}
 
-    need_filter_signature: Signal<bool>,
-    filtering_signatures: Signal<String>,
+impl FilterItemKind {
+    const ALL: [FilterItemKind; 9] = [
+        FilterItemKind::Signature,
+        FilterItemKind::Account,
+        FilterItemKind::Status,
+        FilterItemKind::CircleSwap,
+        FilterItemKind::ProgramId,
+        FilterItemKind::Fee,
+        FilterItemKind::ComputeUnits,
+        FilterItemKind::LogMessage,
+        FilterItemKind::TokenBalance,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            FilterItemKind::Signature => "包含签名",
+            FilterItemKind::Account => "包含帐号",
+            FilterItemKind::Status => "状态过滤(成功/失败)",
+            FilterItemKind::CircleSwap => "三角套利过滤",
+            FilterItemKind::ProgramId => "调用程序",
+            FilterItemKind::Fee => "手续费范围",
+            FilterItemKind::ComputeUnits => "消耗CU范围",
+            FilterItemKind::LogMessage => "日志匹配",
+            FilterItemKind::TokenBalance => "代币余额变化",
+        }
+    }
 
-    need_filter_status: Signal<bool>,
-    // true: success, false: false
-    filtering_status: Signal<bool>,
+    fn key(&self) -> &'static str {
+        match self {
+            FilterItemKind::Signature => "signature",
+            FilterItemKind::Account => "account",
+            FilterItemKind::Status => "status",
+            FilterItemKind::CircleSwap => "circle_swap",
+            FilterItemKind::ProgramId => "program_id",
+            FilterItemKind::Fee => "fee",
+            FilterItemKind::ComputeUnits => "compute_units",
+            FilterItemKind::LogMessage => "log_message",
+            FilterItemKind::TokenBalance => "token_balance",
+        }
+    }
 
-    need_filter_circle_swap: Signal<bool>,
-    // transaction_service_state: TransactionServiceState,
+    fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|k| k.key() == key)
+    }
 }
 
-impl FilterContext {
-    fn new() -> Self {
-        let need_filter_account = Signal::new(false);
-        let filtering_accounts = Signal::new("".into());
+/// 筛选项的输入值，按种类区分
+#[derive(Clone, Copy, PartialEq)]
+enum FilterItemValue {
+    Signature(Signal<String>),
+    Account(Signal<String>),
+    // true: success, false: false
+    Status(Signal<bool>),
+    CircleSwap,
+    ProgramId {
+        program_ids: Signal<String>,
+        // true: 包含CPI调用, false: 只匹配顶层指令
+        including_cpi: Signal<bool>,
+    },
+    Fee {
+        min: Signal<String>,
+        max: Signal<String>,
+        // true: SOL, false: lamports
+        in_sol: Signal<bool>,
+    },
+    ComputeUnits {
+        min: Signal<String>,
+        max: Signal<String>,
+    },
+    LogMessage {
+        content: Signal<String>,
+        // true: 正则表达式, false: 子串
+        use_regex: Signal<bool>,
+        // 为空时匹配所有日志
+        program_id: Signal<String>,
+    },
+    TokenBalance {
+        // 为空时不限制
+        mint: Signal<String>,
+        owner: Signal<String>,
+        direction: Signal<TokenBalanceDirection>,
+        // 按精度换算后的最小变化量，为空时不限制
+        min_change: Signal<String>,
+    },
+}
 
-        let need_filter_signature = Signal::new(false);
+impl FilterItemValue {
+    fn new(kind: FilterItemKind) -> Self {
+        match kind {
+            FilterItemKind::Signature => FilterItemValue::Signature(Signal::new("".into())),
+            FilterItemKind::Account => FilterItemValue::Account(Signal::new("".into())),
+            FilterItemKind::Status => FilterItemValue::Status(Signal::new(false)),
+            FilterItemKind::CircleSwap => FilterItemValue::CircleSwap,
+            FilterItemKind::ProgramId => FilterItemValue::ProgramId {
+                program_ids: Signal::new("".into()),
+                including_cpi: Signal::new(true),
+            },
+            FilterItemKind::Fee => FilterItemValue::Fee {
+                min: Signal::new("".into()),
+                max: Signal::new("".into()),
+                in_sol: Signal::new(false),
+            },
+            FilterItemKind::ComputeUnits => FilterItemValue::ComputeUnits {
+                min: Signal::new("".into()),
+                max: Signal::new("".into()),
+            },
+            FilterItemKind::LogMessage => FilterItemValue::LogMessage {
+                content: Signal::new("".into()),
+                use_regex: Signal::new(false),
+                program_id: Signal::new("".into()),
+            },
+            FilterItemKind::TokenBalance => FilterItemValue::TokenBalance {
+                mint: Signal::new("".into()),
+                owner: Signal::new("".into()),
+                direction: Signal::new(TokenBalanceDirection::Any),
+                min_change: Signal::new("".into()),
+            },
+        }
+    }
 
-        let need_filter_circle_swap = Signal::new(false);
-        let filtering_signatures = Signal::new("".into());
+    /// 以已保存的配置填充输入值
+    fn from_spec(spec: &FilterItemSpec) -> Self {
+        let optional = |v: &Option<u64>| v.map(|v| v.to_string()).unwrap_or_default();
+        match spec {
+            FilterItemSpec::Signature { signatures } => {
+                FilterItemValue::Signature(Signal::new(signatures.join(",")))
+            }
+            FilterItemSpec::Account { accounts } => {
+                FilterItemValue::Account(Signal::new(accounts.join(",")))
+            }
+            FilterItemSpec::Status { success } => FilterItemValue::Status(Signal::new(*success)),
+            FilterItemSpec::CircleSwap => FilterItemValue::CircleSwap,
+            FilterItemSpec::ProgramId {
+                program_ids,
+                including_cpi,
+            } => FilterItemValue::ProgramId {
+                program_ids: Signal::new(program_ids.join(",")),
+                including_cpi: Signal::new(*including_cpi),
+            },
+            FilterItemSpec::Fee { min, max } => FilterItemValue::Fee {
+                min: Signal::new(optional(min)),
+                max: Signal::new(optional(max)),
+                in_sol: Signal::new(false),
+            },
+            FilterItemSpec::ComputeUnits { min, max } => FilterItemValue::ComputeUnits {
+                min: Signal::new(optional(min)),
+                max: Signal::new(optional(max)),
+            },
+            FilterItemSpec::LogMessage {
+                content,
+                use_regex,
+                program_id,
+            } => FilterItemValue::LogMessage {
+                content: Signal::new(content.clone()),
+                use_regex: Signal::new(*use_regex),
+                program_id: Signal::new(program_id.clone().unwrap_or_default()),
+            },
+            FilterItemSpec::TokenBalance {
+                mint,
+                owner,
+                direction,
+                min_change,
+            } => FilterItemValue::TokenBalance {
+                mint: Signal::new(mint.clone().unwrap_or_default()),
+                owner: Signal::new(owner.clone().unwrap_or_default()),
+                direction: Signal::new(*direction),
+                min_change: Signal::new(min_change.map(|m| m.to_string()).unwrap_or_default()),
+            },
+        }
+    }
 
-        let need_filter_status = Signal::new(false);
-        let filtering_status = Signal::new(false);
+    fn kind(&self) -> FilterItemKind {
+        match self {
+            FilterItemValue::Signature(_) => FilterItemKind::Signature,
+            FilterItemValue::Account(_) => FilterItemKind::Account,
+            FilterItemValue::Status(_) => FilterItemKind::Status,
+            FilterItemValue::CircleSwap => FilterItemKind::CircleSwap,
+            FilterItemValue::ProgramId { .. } => FilterItemKind::ProgramId,
+            FilterItemValue::Fee { .. } => FilterItemKind::Fee,
+            FilterItemValue::ComputeUnits { .. } => FilterItemKind::ComputeUnits,
+            FilterItemValue::LogMessage { .. } => FilterItemKind::LogMessage,
+            FilterItemValue::TokenBalance { .. } => FilterItemKind::TokenBalance,
+        }
+    }
+}
 
-        FilterContext {
-            need_filter_account,
-            need_filter_signature,
-            filtering_accounts,
-            need_filter_circle_swap,
-            filtering_signatures,
-            need_filter_status,
-            filtering_status,
+/// 单个筛选项，id在筛选项创建时生成，并作为其在过滤表达式中的标识
+#[derive(Clone, Copy, PartialEq)]
+struct FilterItemContext {
+    id: TransactionFilterId,
+    /// 为true时排除满足此筛选项的交易
+    exclude: Signal<bool>,
+    value: FilterItemValue,
+    /// 最近一次筛选时输入内容的解析错误
+    error: Signal<Option<String>>,
+}
+
+impl FilterItemContext {
+    fn new(kind: FilterItemKind) -> Self {
+        FilterItemContext {
+            id: TransactionFilterId::next(),
+            exclude: Signal::new(false),
+            value: FilterItemValue::new(kind),
+            error: Signal::new(None),
         }
     }
 
-    // fn apply_filters(&mut self) {
-    //     // 收集过滤器
-    //     let mut filters: HashMap<
-    //         TypeId,
-    //         Box<dyn TransactionFilter<ContextType = TransactionFilterContext>>,
-    //     > = HashMap::new();
-    //
-    //     self.collect_filter(&mut filters, self.make_status_filter());
-    //     self.collect_filter(&mut filters, self.make_signature_filter());
-    //     self.collect_filter(&mut filters, self.make_account_filter());
-    //     self.collect_filter(&mut filters, self.make_circle_swap_filter());
-    //
-    //     self.transaction_service_state
-    //         .set_and_apply_filters(filters);
-    // }
-
-    fn make_circle_swap_filter(&self) -> anyhow::Result<Option<CircleSwapFilter>> {
-        if *self.need_filter_circle_swap.peek_unchecked() {
-            Ok(Some(CircleSwapFilter))
-        } else {
-            Ok(None)
+    fn from_config(config: &FilterItemConfig) -> Self {
+        FilterItemContext {
+            id: config.id,
+            exclude: Signal::new(config.exclude),
+            value: FilterItemValue::from_spec(&config.spec),
+            error: Signal::new(None),
         }
     }
 
-    fn make_account_filter(&self) -> anyhow::Result<Option<AccountFilter>> {
-        if !*(self.need_filter_account.peek_unchecked()) {
-            return Ok(None);
+    fn make_spec(&self) -> anyhow::Result<Option<FilterItemSpec>> {
+        match self.value {
+            FilterItemValue::Signature(content) => make_signature_spec(&content.peek_unchecked()),
+            FilterItemValue::Account(content) => make_account_spec(&content.peek_unchecked()),
+            FilterItemValue::Status(success) => Ok(Some(FilterItemSpec::Status {
+                success: *success.peek_unchecked(),
+            })),
+            FilterItemValue::CircleSwap => Ok(Some(FilterItemSpec::CircleSwap)),
+            FilterItemValue::ProgramId {
+                program_ids,
+                including_cpi,
+            } => make_program_id_spec(
+                &program_ids.peek_unchecked(),
+                *including_cpi.peek_unchecked(),
+            ),
+            FilterItemValue::Fee { min, max, in_sol } => make_fee_spec(
+                &min.peek_unchecked(),
+                &max.peek_unchecked(),
+                *in_sol.peek_unchecked(),
+            ),
+            FilterItemValue::ComputeUnits { min, max } => {
+                make_compute_unit_spec(&min.peek_unchecked(), &max.peek_unchecked())
+            }
+            FilterItemValue::LogMessage {
+                content,
+                use_regex,
+                program_id,
+            } => make_log_message_spec(
+                &content.peek_unchecked(),
+                *use_regex.peek_unchecked(),
+                &program_id.peek_unchecked(),
+            ),
+            FilterItemValue::TokenBalance {
+                mint,
+                owner,
+                direction,
+                min_change,
+            } => make_token_balance_spec(
+                &mint.peek_unchecked(),
+                &owner.peek_unchecked(),
+                *direction.peek_unchecked(),
+                &min_change.peek_unchecked(),
+            ),
         }
-        let content = &*self.filtering_accounts.peek_unchecked();
+    }
 
-        let trimmed = content.trim();
-        if trimmed.is_empty() {
-            return Ok(None);
+    /// 收集筛选项的配置，输入内容无法解析时忽略该筛选项，并在筛选项下显示错误
+    fn collect_config(&self) -> Option<FilterItemConfig> {
+        let mut error = self.error;
+        match self.make_spec() {
+            Ok(spec) => {
+                error.set(None);
+                spec.map(|spec| {
+                    FilterItemConfig::new(self.id, *self.exclude.peek_unchecked(), spec)
+                })
+            }
+            Err(e) => {
+                error.set(Some(format!("{e:#}")));
+                None
+            }
         }
+    }
+}
 
-        let accounts = trimmed
-            .split(",")
-            .map(|s| s.to_string())
-            .collect::<Vec<_>>();
+/// 一个筛选组中的所有筛选项，同类型的筛选项可以同时存在多个
+#[derive(Clone, Copy, PartialEq)]
+struct FilterContext {
+    items: Signal<Vec<FilterItemContext>>,
+}
 
-        Ok(Some(AccountFilter::Include(accounts)))
+impl FilterContext {
+    fn new() -> Self {
+        FilterContext {
+            items: Signal::new(Vec::new()),
+        }
     }
 
-    fn make_status_filter(&self) -> anyhow::Result<Option<TransactionStatusFilter>> {
-        if *self.need_filter_status.peek_unchecked() {
-            Ok(Some(if *self.filtering_status.peek_unchecked() {
-                TransactionStatusFilter::success()
-            } else {
-                TransactionStatusFilter::fail()
-            }))
-        } else {
-            Ok(None)
+    fn from_configs(configs: &[FilterItemConfig]) -> Self {
+        FilterContext {
+            items: Signal::new(configs.iter().map(FilterItemContext::from_config).collect()),
         }
     }
 
-    fn make_signature_filter(&self) -> anyhow::Result<Option<SignatureFilter>> {
-        if !*(self.need_filter_signature.peek_unchecked()) {
-            return Ok(None);
-        }
-        let content = &*self.filtering_signatures.peek_unchecked();
+    fn add(&mut self, kind: FilterItemKind) {
+        self.items.write().push(FilterItemContext::new(kind));
+    }
 
+    fn remove(&mut self, id: TransactionFilterId) {
+        self.items.write().retain(|item| item.id != id);
+    }
+
+    pub fn collect_config(&self) -> Vec<FilterItemConfig> {
+        self.items
+            .peek_unchecked()
+            .iter()
+            .filter_map(|item| item.collect_config())
+            .collect()
+    }
+}
+
+fn split_list(content: &str) -> Option<Vec<String>> {
+    let trimmed = content.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+
+    Some(
+        trimmed
+            .split(",")
+            .map(|s| s.trim().to_string())
+            .collect::<Vec<_>>(),
+    )
+}
+
+fn make_account_spec(content: &str) -> anyhow::Result<Option<FilterItemSpec>> {
+    Ok(split_list(content).map(|accounts| FilterItemSpec::Account { accounts }))
+}
+
+fn make_signature_spec(content: &str) -> anyhow::Result<Option<FilterItemSpec>> {
+    Ok(split_list(content).map(|signatures| FilterItemSpec::Signature { signatures }))
+}
+
+fn make_program_id_spec(
+    content: &str,
+    including_cpi: bool,
+) -> anyhow::Result<Option<FilterItemSpec>> {
+    Ok(split_list(content).map(|program_ids| FilterItemSpec::ProgramId {
+        program_ids,
+        including_cpi,
+    }))
+}
+
+fn make_fee_spec(min: &str, max: &str, in_sol: bool) -> anyhow::Result<Option<FilterItemSpec>> {
+    let parse = |content: &str| -> anyhow::Result<Option<u64>> {
         let trimmed = content.trim();
         if trimmed.is_empty() {
-            return Ok(None);
+            Ok(None)
+        } else if in_sol {
+            Ok(Some(parse_sol_to_lamports(trimmed)?))
+        } else {
+            Ok(Some(trimmed.parse::<u64>()?))
         }
+    };
+    let (min, max) = (parse(min)?, parse(max)?);
+    if min.is_none() && max.is_none() {
+        return Ok(None);
+    }
 
-        let accounts = trimmed
-            .split(",")
-            .map(|s| s.to_string())
-            .collect::<Vec<_>>();
+    Ok(Some(FilterItemSpec::Fee { min, max }))
+}
 
-        Ok(Some(SignatureFilter::Include(accounts)))
+fn make_compute_unit_spec(min: &str, max: &str) -> anyhow::Result<Option<FilterItemSpec>> {
+    let parse = |content: &str| -> anyhow::Result<Option<u64>> {
+        let trimmed = content.trim();
+        if trimmed.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(trimmed.parse::<u64>()?))
+        }
+    };
+    let (min, max) = (parse(min)?, parse(max)?);
+    if min.is_none() && max.is_none() {
+        return Ok(None);
     }
 
-    pub fn collect_filter(
-        &self,
-    ) -> HashMap<TypeId, Box<dyn TransactionFilter<ContextType = TransactionFilterContext>>> {
-        let mut filters = HashMap::new();
-        self.do_collect_filter(&mut filters, self.make_status_filter());
-        self.do_collect_filter(&mut filters, self.make_signature_filter());
-        self.do_collect_filter(&mut filters, self.make_account_filter());
-        self.do_collect_filter(&mut filters, self.make_circle_swap_filter());
+    Ok(Some(FilterItemSpec::ComputeUnits { min, max }))
+}
 
-        filters
+fn make_log_message_spec(
+    content: &str,
+    use_regex: bool,
+    program_id: &str,
+) -> anyhow::Result<Option<FilterItemSpec>> {
+    // 只用trim判断是否为空，首尾空白可能是要匹配的内容，保留原始输入
+    if content.trim().is_empty() {
+        return Ok(None);
     }
 
-    fn do_collect_filter(
-        &self,
-        container: &mut HashMap<
-            TypeId,
-            Box<dyn TransactionFilter<ContextType = TransactionFilterContext>>,
-        >,
-        filter: anyhow::Result<
-            Option<impl TransactionFilter<ContextType = TransactionFilterContext>>,
-        >,
-    ) {
-        if let Ok(filter_context) = filter {
-            if let Some(filter_context) = filter_context {
-                let filter = Box::new(filter_context)
-                    as Box<dyn TransactionFilter<ContextType = TransactionFilterContext>>;
-                container.insert(filter.id(), filter);
-            }
-        }
+    let program_id = program_id.trim();
+    let program_id = (!program_id.is_empty()).then(|| program_id.to_string());
+    // 提前构建一次，以便正则表达式错误在面板中即可发现
+    if use_regex {
+        LogMessageFilter::regex(content, None)?;
     }
+
+    Ok(Some(FilterItemSpec::LogMessage {
+        content: content.to_string(),
+        use_regex,
+        program_id,
+    }))
+}
+
+fn make_token_balance_spec(
+    mint: &str,
+    owner: &str,
+    direction: TokenBalanceDirection,
+    min_change: &str,
+) -> anyhow::Result<Option<FilterItemSpec>> {
+    let optional = |content: &str| {
+        let trimmed = content.trim();
+        (!trimmed.is_empty()).then(|| trimmed.to_string())
+    };
+    let min_change = match min_change.trim() {
+        "" => None,
+        trimmed => Some(trimmed.parse::<f64>()?),
+    };
+
+    Ok(Some(FilterItemSpec::TokenBalance {
+        mint: optional(mint),
+        owner: optional(owner),
+        direction,
+        min_change,
+    }))
 }
 
+static NEXT_FILTER_GROUP_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// 过滤组，组内的过滤器与子组按照operator组合，negated为true时对整个组取反
 #[derive(Clone, Copy, PartialEq)]
-enum FilterItemsKind {
-    Main,
-    Additional,
+struct FilterGroupContext {
+    id: usize,
+    operator: Signal<FilterGroupOperator>,
+    negated: Signal<bool>,
+    filters: FilterContext,
+    children: Signal<Vec<FilterGroupContext>>,
+}
+
+impl FilterGroupContext {
+    fn new() -> Self {
+        FilterGroupContext {
+            id: NEXT_FILTER_GROUP_ID.fetch_add(1, Ordering::Relaxed),
+            operator: Signal::new(FilterGroupOperator::And),
+            negated: Signal::new(false),
+            filters: FilterContext::new(),
+            children: Signal::new(Vec::new()),
+        }
+    }
+
+    /// 以已保存的配置恢复本组及所有子组
+    fn from_config(config: &FilterGroupConfig) -> Self {
+        FilterGroupContext {
+            id: NEXT_FILTER_GROUP_ID.fetch_add(1, Ordering::Relaxed),
+            operator: Signal::new(config.operator),
+            negated: Signal::new(config.negated),
+            filters: FilterContext::from_configs(&config.items),
+            children: Signal::new(config.children.iter().map(Self::from_config).collect()),
+        }
+    }
+
+    /// 递归收集本组及所有子组的配置
+    fn collect_config(&self) -> FilterGroupConfig {
+        FilterGroupConfig {
+            operator: *self.operator.peek_unchecked(),
+            negated: *self.negated.peek_unchecked(),
+            items: self.filters.collect_config(),
+            children: self
+                .children
+                .peek_unchecked()
+                .iter()
+                .map(|c| c.collect_config())
+                .collect(),
+        }
+    }
 }
 
 #[component]
 pub fn TransactionFilterPanel() -> Element {
     let workspace_state = use_context::<WorkspaceState>();
     let mut transaction_service_state = workspace_state.transaction_service_state;
-    let filter_context = use_context_provider(|| FilterContext::new());
-    let additional_filter_contexts = use_signal(|| Vec::new());
-    let mut additional_filter_contexts: Signal<Vec<FilterContext>> =
-        use_context_provider(|| additional_filter_contexts);
-    let additional_filters_len = additional_filter_contexts.read_unchecked().len();
+    // 从已保存的配置恢复，通过分享链接打开时即为链接中的筛选条件
+    let root = use_hook(|| {
+        FilterGroupContext::from_config(
+            &transaction_service_state.transaction_filter_config.peek(),
+        )
+    });
     let on_click_filter = move |_e| {
-        let main = filter_context.collect_filter();
-        let additional = additional_filter_contexts
-            .read_unchecked()
-            .iter()
-            .map(|c| c.collect_filter())
-            .collect::<Vec<_>>();
-        transaction_service_state.set_and_apply_filters(main, additional);
-    };
-
-    let on_click_add_additional_filter = move |e| {
-        additional_filter_contexts
-            .write()
-            .push(FilterContext::new());
+        transaction_service_state.set_and_apply_filter_config(root.collect_config());
     };
 
     rsx! {
@@ -202,22 +533,9 @@ pub fn TransactionFilterPanel() -> Element {
                 "交易筛选器"
             }
 
-            TransactionFilterItems {
-                kind: FilterItemsKind::Main,
-                idx: 0,
-            }
-
-            for idx in 0..additional_filters_len {
-                TransactionFilterItems {
-                    kind: FilterItemsKind::Additional,
-                    idx,
-                }
-            }
-
-            button {
-                id: "add_additional_filter_button",
-                onclick: on_click_add_additional_filter,
-                "增加附加筛选器"
+            TransactionFilterGroup {
+                group: root,
+                parent: None,
             }
 
             button {
@@ -230,61 +548,58 @@ pub fn TransactionFilterPanel() -> Element {
 }
 
 #[component]
-fn TransactionFilterItems(kind: FilterItemsKind, idx: usize) -> Element {
-    let (
-        FilterContext {
-            need_filter_account,
-            filtering_accounts,
-            need_filter_signature,
-            filtering_signatures,
-            need_filter_status,
-            mut filtering_status,
-            need_filter_circle_swap,
-        },
-        flag,
-        is_main,
-    ) = match kind {
-        FilterItemsKind::Main => (use_context::<FilterContext>(), "主", true),
-        FilterItemsKind::Additional => {
-            let all = use_context::<Signal<Vec<FilterContext>>>();
-            (all.read_unchecked()[idx], "附", false)
-        }
-    };
-
-    let additional = use_context::<Signal<Vec<FilterContext>>>();
-
-    let check_cb = |mut sig: Signal<bool>| {
-        move |event: Event<FormData>| {
-            sig.set(event.checked());
+fn TransactionFilterGroup(
+    group: FilterGroupContext,
+    parent: Option<Signal<Vec<FilterGroupContext>>>,
+) -> Element {
+    let FilterGroupContext {
+        id,
+        mut operator,
+        mut negated,
+        filters,
+        mut children,
+    } = group;
+    let is_root = parent.is_none();
+    let on_operator_change = move |e: Event<FormData>| {
+        if e.value() == "or" {
+            operator.set(FilterGroupOperator::Or);
+        } else {
+            operator.set(FilterGroupOperator::And);
         }
     };
-    let input_cb = |mut sig: Signal<String>| {
-        move |event: Event<FormData>| {
-            sig.set(event.value());
-        }
+    let on_click_add_child = move |_| {
+        children.write().push(FilterGroupContext::new());
     };
-    let on_status_filter_submit = move |e: Event<FormData>| {
-        info!("on cliick status filter radio: {}", e.data.value());
-        if e.data.value() == "true" {
-            filtering_status.set(true);
-        } else {
-            filtering_status.set(false);
+    let on_remove = move |_| {
+        if let Some(mut parent) = parent {
+            parent.write().retain(|c| c.id != id);
         }
     };
-    let on_remove = move |e| {
-        additional.write_unchecked().remove(idx);
-    };
+    let is_or = *operator.read() == FilterGroupOperator::Or;
+    let child_groups = children.read().clone();
 
     rsx! {
         div {
-            id: "filter_items_container",
+            class: "filter_group_container",
             div {
                 class: "filter_items_flag_container",
                 div {
-                    class: "filter_items_kind",
-                   {flag}
+                    class: "filter_group_operator_container",
+                    label {
+                        input {
+                            r#type: "checkbox",
+                            checked: negated(),
+                            onchange: move |e: Event<FormData>| negated.set(e.checked()),
+                        }
+                        "非"
+                    }
+                    select {
+                        onchange: on_operator_change,
+                        option { value: "and", selected: !is_or, "且(全部满足)" }
+                        option { value: "or", selected: is_or, "或(任一满足)" }
+                    }
                 }
-                if !is_main {
+                if !is_root {
                     button {
                         class: "additional_filter_remove_button",
                         onclick: on_remove,
@@ -292,88 +607,352 @@ fn TransactionFilterItems(kind: FilterItemsKind, idx: usize) -> Element {
                     }
                 }
             }
-            // 签名过滤
-            div {
-                class: "two_line_filter_item_container",
-               div {
-                   class: "filter_item_title_container",
-                   input{
-                       r#type: "checkbox",
-                       onchange: check_cb(need_filter_signature),
-                   }
-                   label{"包含签名:"}
-               }
-               input{
-                   class: "filter_item_value",
-                   oninput: input_cb(filtering_signatures),
-               }
 
+            TransactionFilterItems {
+                context: filters,
             }
 
-            // 帐号过滤器
-            div {
-                class: "two_line_filter_item_container",
-               div {
-                   class: "filter_item_title_container",
-                   input{
-                       r#type: "checkbox",
-                       onchange: check_cb(need_filter_account),
-                   }
-                   label{"包含帐号:"}
-               }
-               input{
-                   class: "filter_item_value",
-                   oninput: input_cb(filtering_accounts),
-               }
+            for child in child_groups {
+                TransactionFilterGroup {
+                    key: "{child.id}",
+                    group: child,
+                    parent: Some(children),
+                }
+            }
+
+            button {
+                class: "add_filter_group_button",
+                onclick: on_click_add_child,
+                "增加子筛选组"
+            }
+        }
+    }
+}
+
+#[component]
+fn TransactionFilterItems(context: FilterContext) -> Element {
+    let mut adding_kind = use_signal(|| FilterItemKind::Signature);
+    let on_kind_change = move |e: Event<FormData>| {
+        if let Some(kind) = FilterItemKind::from_key(&e.value()) {
+            adding_kind.set(kind);
+        }
+    };
+    let on_click_add = move |_| {
+        let mut context = context;
+        context.add(adding_kind());
+    };
+    let items = context.items.read().clone();
 
+    rsx! {
+        div {
+            id: "filter_items_container",
+            for item in items {
+                TransactionFilterItem {
+                    key: "{item.id}",
+                    item,
+                    context,
+                }
             }
             div {
-               div {
-                   class: "tx_status_filter_title",
-                   input{
-                       r#type: "checkbox",
-                       onchange: check_cb(need_filter_status),
-                   }
-                   label{"状态过滤(成功/失败): "}
-               }
+                class: "filter_item_add_container",
+                select {
+                    onchange: on_kind_change,
+                    for kind in FilterItemKind::ALL {
+                        option {
+                            value: kind.key(),
+                            selected: kind == adding_kind(),
+                            {kind.name()}
+                        }
+                    }
+                }
+                button {
+                    onclick: on_click_add,
+                    "添加筛选条件"
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn TransactionFilterItem(item: FilterItemContext, context: FilterContext) -> Element {
+    let FilterItemContext {
+        id,
+        mut exclude,
+        value,
+        error,
+    } = item;
+    let workspace_state = use_context::<WorkspaceState>();
+    let statistic = workspace_state
+        .transaction_service_state
+        .transaction_filter_statistics
+        .read()
+        .get(&id)
+        .copied();
+    // 输入解析错误优先，否则显示最近一次应用筛选时构建过滤器的错误
+    let error = error().or_else(|| {
+        workspace_state
+            .transaction_service_state
+            .transaction_filter_errors
+            .read()
+            .iter()
+            .find(|e| e.id == id)
+            .map(|e| e.message.clone())
+    });
+    let input_cb = |mut sig: Signal<String>| {
+        move |event: Event<FormData>| {
+            sig.set(event.value());
+        }
+    };
+    let on_remove = move |_| {
+        let mut context = context;
+        context.remove(id);
+    };
+    let input = match value {
+        FilterItemValue::Signature(content) | FilterItemValue::Account(content) => rsx! {
+            input {
+                class: "filter_item_value",
+                value: "{content}",
+                oninput: input_cb(content),
+            }
+        },
+        FilterItemValue::Status(mut filtering_status) => {
+            let name = format!("status_{id}");
+            let on_status_filter_submit = move |e: Event<FormData>| {
+                info!("on cliick status filter radio: {}", e.data.value());
+                filtering_status.set(e.data.value() == "true");
+            };
+            rsx! {
                 form {
-                    id: "tx_status_er",
+                    class: "tx_status_filter_form",
                     label {
                         input {
                             onchange: on_status_filter_submit,
-                            name: "status",
+                            name: "{name}",
                             r#type: "radio",
-                            value: "true"
+                            value: "true",
+                            checked: filtering_status(),
                         }
                         "成功"
                     }
                     label {
                         input {
                             onchange: on_status_filter_submit,
-                            name: "status",
+                            name: "{name}",
                             r#type: "radio",
-                            value: "false"
+                            value: "false",
+                            checked: !filtering_status(),
                         }
                         "失败"
                     }
                 }
             }
+        }
+        FilterItemValue::CircleSwap => rsx! {},
+        FilterItemValue::ProgramId {
+            program_ids,
+            mut including_cpi,
+        } => {
+            let name = format!("program_scope_{id}");
+            let on_scope_change = move |e: Event<FormData>| {
+                including_cpi.set(e.value() == "true");
+            };
+            rsx! {
+                input {
+                    class: "filter_item_value",
+                    placeholder: "程序ID，多个以逗号分隔",
+                    value: "{program_ids}",
+                    oninput: input_cb(program_ids),
+                }
+                form {
+                    class: "tx_status_filter_form",
+                    label {
+                        input {
+                            onchange: on_scope_change,
+                            name: "{name}",
+                            r#type: "radio",
+                            value: "false",
+                            checked: !including_cpi(),
+                        }
+                        "仅顶层指令"
+                    }
+                    label {
+                        input {
+                            onchange: on_scope_change,
+                            name: "{name}",
+                            r#type: "radio",
+                            value: "true",
+                            checked: including_cpi(),
+                        }
+                        "包含CPI"
+                    }
+                }
+            }
+        }
+        FilterItemValue::Fee {
+            min,
+            max,
+            mut in_sol,
+        } => {
+            let unit = if in_sol() { "SOL" } else { "lamports" };
+            rsx! {
+                div {
+                    class: "filter_item_range_container",
+                    input {
+                        class: "filter_item_value",
+                        placeholder: "最小({unit})",
+                        value: "{min}",
+                        oninput: input_cb(min),
+                    }
+                    input {
+                        class: "filter_item_value",
+                        placeholder: "最大({unit})",
+                        value: "{max}",
+                        oninput: input_cb(max),
+                    }
+                }
+                label {
+                    input {
+                        r#type: "checkbox",
+                        checked: in_sol(),
+                        onchange: move |e: Event<FormData>| in_sol.set(e.checked()),
+                    }
+                    "以SOL为单位"
+                }
+            }
+        }
+        FilterItemValue::ComputeUnits { min, max } => rsx! {
             div {
-                // 三角套利过滤器
-               div {
-                    id: "circle_filter_check_box",
-                   class: "filter_item_title_container",
-                   input{
-                       r#type: "checkbox",
-                       onchange: check_cb(need_filter_circle_swap),
-                   }
-                   label{
-                        r#for: "circle_filter_check_box",
-                        "三角套利过滤"
+                class: "filter_item_range_container",
+                input {
+                    class: "filter_item_value",
+                    placeholder: "最小CU",
+                    value: "{min}",
+                    oninput: input_cb(min),
+                }
+                input {
+                    class: "filter_item_value",
+                    placeholder: "最大CU",
+                    value: "{max}",
+                    oninput: input_cb(max),
+                }
+            }
+        },
+        FilterItemValue::LogMessage {
+            content,
+            mut use_regex,
+            program_id,
+        } => rsx! {
+            input {
+                class: "filter_item_value",
+                placeholder: "如: Program log: Instruction: Swap",
+                value: "{content}",
+                oninput: input_cb(content),
+            }
+            label {
+                input {
+                    r#type: "checkbox",
+                    checked: use_regex(),
+                    onchange: move |e: Event<FormData>| use_regex.set(e.checked()),
+                }
+                "正则表达式"
+            }
+            input {
+                class: "filter_item_value",
+                placeholder: "仅匹配此程序输出的日志(可选)",
+                value: "{program_id}",
+                oninput: input_cb(program_id),
+            }
+        },
+        FilterItemValue::TokenBalance {
+            mint,
+            owner,
+            mut direction,
+            min_change,
+        } => {
+            let on_direction_change = move |e: Event<FormData>| {
+                direction.set(match e.value().as_str() {
+                    "increase" => TokenBalanceDirection::Increase,
+                    "decrease" => TokenBalanceDirection::Decrease,
+                    _ => TokenBalanceDirection::Any,
+                });
+            };
+            let current = direction();
+            rsx! {
+                input {
+                    class: "filter_item_value",
+                    placeholder: "代币mint(可选)",
+                    value: "{mint}",
+                    oninput: input_cb(mint),
+                }
+                input {
+                    class: "filter_item_value",
+                    placeholder: "持有者(可选)",
+                    value: "{owner}",
+                    oninput: input_cb(owner),
+                }
+                div {
+                    class: "filter_item_range_container",
+                    select {
+                        onchange: on_direction_change,
+                        option { value: "any", selected: current == TokenBalanceDirection::Any, "有变化" }
+                        option { value: "increase", selected: current == TokenBalanceDirection::Increase, "增加" }
+                        option { value: "decrease", selected: current == TokenBalanceDirection::Decrease, "减少" }
                     }
-               }
-
+                    input {
+                        class: "filter_item_value",
+                        placeholder: "最小变化量(可选)",
+                        value: "{min_change}",
+                        oninput: input_cb(min_change),
+                    }
+                }
+            }
+        }
+    };
 
+    rsx! {
+        div {
+            class: "two_line_filter_item_container",
+            div {
+                class: "filter_items_flag_container",
+                div {
+                    class: "filter_item_title_container",
+                    label { "{value.kind().name()}:" }
+                    label {
+                        input {
+                            r#type: "checkbox",
+                            checked: exclude(),
+                            onchange: move |e: Event<FormData>| exclude.set(e.checked()),
+                        }
+                        "排除"
+                    }
+                }
+                button {
+                    class: "additional_filter_remove_button",
+                    onclick: on_remove,
+                    "移除"
+                }
+            }
+            {input}
+            if let Some(error) = error {
+                div {
+                    class: "filter_item_error",
+                    "{error}"
+                }
+            }
+            if let Some(statistic) = statistic {
+                // 统计的是筛选项本身的结果，排除时匹配的交易会被排除
+                if exclude() {
+                    div {
+                        class: "filter_item_statistic",
+                        "检查 {statistic.evaluated} / 匹配(已排除) {statistic.passed} / 未匹配(保留) {statistic.rejected}"
+                    }
+                } else {
+                    div {
+                        class: "filter_item_statistic",
+                        "检查 {statistic.evaluated} / 通过 {statistic.passed} / 拒绝 {statistic.rejected}"
+                    }
+                }
             }
         }
     }
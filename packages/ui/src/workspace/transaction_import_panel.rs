@@ -0,0 +1,86 @@
+use dioxus::html::{FileEngine, HasFileData};
+use dioxus::logger::tracing::error;
+use dioxus::prelude::*;
+use models::WorkspaceState;
+use std::sync::Arc;
+
+const STYLE: Asset = asset!("/assets/styling/workspace/transaction_import_panel.css");
+
+/// 导入getTransaction/getBlock结果的JSON文件，web端支持拖放，桌面端通过文件选择框选择
+#[component]
+pub fn TransactionImportPanel() -> Element {
+    let workspace = use_context::<WorkspaceState>();
+    let import_error = workspace
+        .transaction_service_state
+        .transaction_import_error
+        .read()
+        .clone();
+    let mut dragging = use_signal(|| false);
+    let mut block_slot = use_signal(String::new);
+
+    let import_files = move |files: Arc<dyn FileEngine>| async move {
+        // getBlock的结果中没有slot，未填写时从文件名中获取
+        let slot = block_slot.peek().trim().parse::<u64>().ok();
+        for name in files.files() {
+            match files.read_file_to_string(&name).await {
+                Some(content) => workspace.import_json(name, content, slot),
+                None => error!("读取文件: {name} 失败"),
+            }
+        }
+    };
+
+    rsx! {
+        document::Stylesheet{href: STYLE}
+        div {
+            id: "tx_import_panel_container",
+            div {
+                style: "font-size: 1.5rem",
+                "导入数据"
+            }
+            div {
+                class: if dragging() { "tx_import_drop_zone tx_import_drop_zone_active" } else { "tx_import_drop_zone" },
+                ondragover: move |e| {
+                    e.prevent_default();
+                    dragging.set(true);
+                },
+                ondragleave: move |_| dragging.set(false),
+                ondrop: move |e: DragEvent| async move {
+                    e.prevent_default();
+                    dragging.set(false);
+                    if let Some(files) = e.files() {
+                        import_files(files).await;
+                    }
+                },
+                "将getTransaction或getBlock结果的JSON文件拖放到此处"
+            }
+            div {
+                label {
+                    r#for: "import_block_slot",
+                    "区块slot "
+                }
+                input {
+                    id: "import_block_slot",
+                    r#type: "number",
+                    placeholder: "导入getBlock结果时填写，为空时从文件名中获取",
+                    oninput: move |data| block_slot.set(data.value()),
+                }
+            }
+            input {
+                r#type: "file",
+                accept: ".json,application/json",
+                multiple: true,
+                onchange: move |e: FormEvent| async move {
+                    if let Some(files) = e.files() {
+                        import_files(files).await;
+                    }
+                },
+            }
+            if let Some(import_error) = import_error {
+                div {
+                    class: "tx_import_error",
+                    "{import_error}"
+                }
+            }
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use dioxus::logger::tracing::{error, info};
 use dioxus::prelude::*;
-use models::workspace::transaction_service::TransactionServiceModule;
+use models::workspace::address_history::{
+    AddressHistoryQuery, DEFAULT_ADDRESS_HISTORY_LIMIT, MAX_ADDRESS_HISTORY_LIMIT,
+};
+use models::workspace::transaction_service::{TransactionServiceModule, TransactionServiceStatus};
 use models::WorkspaceState;
 
 const STYLE: Asset = asset!("/assets/styling/workspace/transaction_info_input_panel.css");
@@ -22,6 +25,12 @@ pub fn TransactionInfoInputPanel() -> Element {
     let mut signature = use_signal(|| String::new());
     let mut backward_slot = use_signal(|| 0);
     let mut forward_slot = use_signal(|| 0);
+    let mut start_slot = use_signal(|| String::new());
+    let mut end_slot = use_signal(|| String::new());
+    let mut address = use_signal(|| String::new());
+    let mut before = use_signal(|| String::new());
+    let mut until = use_signal(|| String::new());
+    let mut limit = use_signal(|| DEFAULT_ADDRESS_HISTORY_LIMIT);
     let on_backward_slot_input = move |data: Event<FormData>| {
         let s: String = data.value();
         let slot = s.parse::<u32>().unwrap_or(0);
@@ -33,9 +42,14 @@ pub fn TransactionInfoInputPanel() -> Element {
         forward_slot.set(slot);
     };
     let need_slot = match &*focus.read_unchecked() {
-        TransactionServiceModule::Query => false,
+        TransactionServiceModule::Query
+        | TransactionServiceModule::QueryBlock
+        | TransactionServiceModule::AddressHistory
+        | TransactionServiceModule::CachedSlots
+        | TransactionServiceModule::Import => false,
         TransactionServiceModule::QueryNearby => true,
     };
+    let current_focus = *focus.read_unchecked();
 
     let on_click = move |_| match focus() {
         TransactionServiceModule::Query => {
@@ -55,6 +69,46 @@ pub fn TransactionInfoInputPanel() -> Element {
                 error!("{e:?}");
             }
         }
+        TransactionServiceModule::QueryBlock => {
+            let start = match start_slot().trim().parse::<u64>() {
+                Ok(slot) => slot,
+                Err(e) => {
+                    error!("起始slot格式错误: {e:?}");
+                    return;
+                }
+            };
+            let end = end_slot();
+            let end = match end.trim() {
+                "" => None,
+                end => match end.parse::<u64>() {
+                    Ok(slot) => Some(slot),
+                    Err(e) => {
+                        error!("结束slot格式错误: {e:?}");
+                        return;
+                    }
+                },
+            };
+            if let Err(e) = workspace_state.query_block(start, end) {
+                error!("{e:?}");
+            }
+        }
+        TransactionServiceModule::AddressHistory => {
+            let cursor = |s: String| {
+                let s = s.trim().to_string();
+                (!s.is_empty()).then_some(s)
+            };
+            let query = AddressHistoryQuery {
+                address: address().trim().to_string(),
+                before: cursor(before()),
+                until: cursor(until()),
+                limit: limit(),
+            };
+            if let Err(e) = workspace_state.query_address_history(query) {
+                error!("{e:?}");
+            }
+        }
+        // 本地缓存及导入数据有单独的面板
+        TransactionServiceModule::CachedSlots | TransactionServiceModule::Import => {}
     };
 
     rsx! {
@@ -67,15 +121,94 @@ pub fn TransactionInfoInputPanel() -> Element {
             }
             div {
                 id: "tx_input_items_container",
-                div {
-                    label {
-                        for: "signature",
-                        {"签名"}
+                if current_focus == TransactionServiceModule::AddressHistory {
+                    div {
+                        label {
+                            r#for: "address",
+                            {"地址"}
+                        }
+                        input {
+                            id: "address",
+                            oninput: move |data| address.set(data.value()),
+                            placeholder: "请输入帐号地址",
+                        }
+                    }
+                    div {
+                        label {
+                            r#for: "before",
+                            {"早于签名 "}
+                        }
+                        input {
+                            id: "before",
+                            oninput: move |data| before.set(data.value()),
+                            placeholder: "可选",
+                        }
+                    }
+                    div {
+                        label {
+                            r#for: "until",
+                            {"晚于签名 "}
+                        }
+                        input {
+                            id: "until",
+                            oninput: move |data| until.set(data.value()),
+                            placeholder: "可选",
+                        }
+                    }
+                    div {
+                        label {
+                            r#for: "limit",
+                            {"数量 "}
+                        }
+                        input {
+                            id: "limit",
+                            type: "number",
+                            placeholder: "{DEFAULT_ADDRESS_HISTORY_LIMIT}",
+                            oninput: move |data: Event<FormData>| {
+                                let value = data
+                                    .value()
+                                    .parse::<u32>()
+                                    .unwrap_or(DEFAULT_ADDRESS_HISTORY_LIMIT);
+                                limit.set(value.min(MAX_ADDRESS_HISTORY_LIMIT));
+                            },
+                        }
+                    }
+                } else if current_focus != TransactionServiceModule::QueryBlock {
+                    div {
+                        label {
+                            for: "signature",
+                            {"签名"}
+                        }
+                        input {
+                            id: "signature",
+                            oninput: move |data| signature.set(data.value()) ,
+                            placeholder: "请输入交易签名",
+                        }
+                    }
+                } else {
+                    div {
+                        label {
+                            r#for: "start_slot",
+                            {"起始slot "}
+                        }
+                        input {
+                            id: "start_slot",
+                            type: "number",
+                            placeholder: "请输入slot",
+                            oninput: move |data| start_slot.set(data.value()),
+                        }
                     }
-                    input {
-                        id: "signature",
-                        oninput: move |data| signature.set(data.value()) ,
-                        placeholder: "请输入交易签名",
+                    div {
+                        label {
+                            r#for: "end_slot",
+                            {"结束slot "}
+                        }
+                        input {
+                            id: "end_slot",
+                            type: "number",
+                            placeholder: "可选，为空时只查询起始slot",
+                            oninput: move |data| end_slot.set(data.value()),
+                        }
                     }
                 }
                 if need_slot {
@@ -110,6 +243,109 @@ pub fn TransactionInfoInputPanel() -> Element {
                 onclick: on_click,
                 "提交"
             }
+            QueryStatus {}
+            CacheStatus {}
+            if current_focus == TransactionServiceModule::AddressHistory {
+                AddressHistoryPager {}
+            }
+        }
+    }
+}
+
+/// 查询状态，查询进行中时可取消，查询失败时显示错误
+#[component]
+fn QueryStatus() -> Element {
+    let workspace_state = use_context::<WorkspaceState>();
+    let status = workspace_state
+        .transaction_service_state
+        .transaction_service_status
+        .read()
+        .clone();
+    let error = workspace_state
+        .transaction_service_state
+        .transaction_service_error
+        .read()
+        .as_ref()
+        .map(|e| e.to_string());
+    let processing = matches!(status, TransactionServiceStatus::Processing(_));
+    let text = match status {
+        TransactionServiceStatus::Idle => return rsx! {},
+        TransactionServiceStatus::Processing(module) => format!("正在{module}..."),
+        TransactionServiceStatus::Finish(module) => match error {
+            Some(e) => format!("{module}失败: {e}"),
+            None => format!("{module}完成"),
+        },
+        TransactionServiceStatus::Cancelled(module) => format!("{module}已取消"),
+    };
+    rsx! {
+        div {
+            class: "query_status",
+            label {
+                "{text}"
+            }
+            if processing {
+                button {
+                    onclick: move |_| workspace_state.cancel_query(),
+                    "取消"
+                }
+            }
+        }
+    }
+}
+
+/// 缓存命中统计
+#[component]
+fn CacheStatus() -> Element {
+    let workspace_state = use_context::<WorkspaceState>();
+    let cache = workspace_state.transaction_service_state.transaction_cache.read();
+    let statistics = cache.statistics();
+    let entries = cache.len();
+    let megabytes = cache.weight() as f64 / (1024.0 * 1024.0);
+    rsx! {
+        div {
+            class: "cache_status",
+            "缓存: 命中 {statistics.hits} / 设备缓存命中 {statistics.persistent_hits} / 未命中 {statistics.misses}, {entries} 项, 约 {megabytes:.1} MB"
+        }
+    }
+}
+
+/// 地址历史交易的翻页按钮，基于最近一次加载的页
+#[component]
+fn AddressHistoryPager() -> Element {
+    let workspace_state = use_context::<WorkspaceState>();
+    let page = workspace_state
+        .transaction_service_state
+        .address_history_page
+        .read()
+        .clone();
+    let Some(page) = page else {
+        return rsx! {};
+    };
+    let older = page.older();
+    let newer = page.newer();
+    let query = move |query: Option<AddressHistoryQuery>| {
+        if let Some(query) = query {
+            if let Err(e) = workspace_state.query_address_history(query) {
+                error!("{e:?}");
+            }
+        }
+    };
+    rsx! {
+        div {
+            class: "address_history_pager",
+            label {
+                "本页 {page.count} 笔交易"
+            }
+            button {
+                disabled: newer.is_none(),
+                onclick: move |_| query(newer.clone()),
+                "更新的交易"
+            }
+            button {
+                disabled: older.is_none(),
+                onclick: move |_| query(older.clone()),
+                "更早的交易"
+            }
         }
     }
 }
@@ -2,6 +2,7 @@ use dioxus::logger::tracing::info;
 use dioxus::prelude::*;
 use models::app::app_state::GlobalService;
 use models::service::service_provider::ServiceProvider;
+use models::workspace::workspace_link::WorkspaceLink;
 use models::{init_network, init_services};
 use ui::{
     Help,
@@ -12,6 +13,8 @@ use ui::{
 
 mod views;
 
+use views::Workspace;
+
 #[derive(Debug, Clone, Routable, PartialEq)]
 #[rustfmt::skip]
 enum Route {
@@ -22,6 +25,8 @@ enum Route {
         Login {},
         #[route("/register")]
         Register {},
+        #[route("/workspace?:..link")]
+        Workspace { link: WorkspaceLink },
         #[route("/:..routes")]
         Help { routes: Vec<String> },
 
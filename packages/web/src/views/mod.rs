@@ -0,0 +1,3 @@
+mod workspace;
+
+pub use workspace::Workspace;
@@ -0,0 +1,18 @@
+use dioxus::prelude::*;
+use models::WorkspaceState;
+use models::workspace::workspace_link::WorkspaceLink;
+
+/// 工作空间页面，通过分享链接打开时复现链接中的查询及筛选条件
+#[component]
+pub fn Workspace(link: WorkspaceLink) -> Element {
+    let workspace = WorkspaceState::use_context();
+    // 在子组件(筛选面板)挂载之前打开链接，使其能以链接中的筛选条件初始化
+    use_hook(move || {
+        if !link.is_empty() {
+            workspace.open_link(link);
+        }
+    });
+    rsx! {
+        ui::Workspace {}
+    }
+}
@@ -0,0 +1,77 @@
+/// 将文本内容保存为文件: web端触发浏览器下载，桌面端弹出保存对话框，
+/// 移动端直接写入应用数据目录。返回false表示用户取消了保存
+pub async fn save_text_file(file_name: &str, mime: &str, content: &str) -> std::io::Result<bool> {
+    imp::save_text_file(file_name, mime, content).await
+}
+
+#[cfg(target_arch = "wasm32")]
+mod imp {
+    use js_sys::Array;
+    use wasm_bindgen::{JsCast, JsValue};
+    use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url, window};
+
+    fn js_error(e: JsValue) -> std::io::Error {
+        std::io::Error::other(format!("{e:?}"))
+    }
+
+    pub async fn save_text_file(
+        file_name: &str,
+        mime: &str,
+        content: &str,
+    ) -> std::io::Result<bool> {
+        let document = window()
+            .and_then(|w| w.document())
+            .ok_or_else(|| std::io::Error::other("无法获取document"))?;
+        let parts = Array::of1(&JsValue::from_str(content));
+        let options = BlobPropertyBag::new();
+        options.set_type(mime);
+        let blob = Blob::new_with_str_sequence_and_options(&parts, &options).map_err(js_error)?;
+        let url = Url::create_object_url_with_blob(&blob).map_err(js_error)?;
+        let anchor = document
+            .create_element("a")
+            .map_err(js_error)?
+            .dyn_into::<HtmlAnchorElement>()
+            .map_err(|e| js_error(e.into()))?;
+        anchor.set_href(&url);
+        anchor.set_download(file_name);
+        anchor.click();
+        Url::revoke_object_url(&url).map_err(js_error)?;
+        Ok(true)
+    }
+}
+
+#[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+mod imp {
+    pub async fn save_text_file(
+        file_name: &str,
+        _mime: &str,
+        content: &str,
+    ) -> std::io::Result<bool> {
+        let Some(handle) = rfd::AsyncFileDialog::new()
+            .set_file_name(file_name)
+            .save_file()
+            .await
+        else {
+            return Ok(false);
+        };
+        handle.write(content.as_bytes()).await?;
+        Ok(true)
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "ios"))]
+mod imp {
+    pub async fn save_text_file(
+        file_name: &str,
+        _mime: &str,
+        content: &str,
+    ) -> std::io::Result<bool> {
+        let dir = dirs::data_dir()
+            .ok_or_else(|| std::io::Error::other("无法获取应用数据目录"))?
+            .join("block_insight")
+            .join("exports");
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(dir.join(file_name), content)?;
+        Ok(true)
+    }
+}
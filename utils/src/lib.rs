@@ -1,4 +1,5 @@
 pub mod context_provider;
+pub mod file_save;
 pub mod storage;
 pub mod time;
 pub mod ws_cross;
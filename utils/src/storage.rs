@@ -1,2 +1,3 @@
 pub mod kv_storage;
-pub mod local_storage;
\ No newline at end of file
+pub mod local_storage;
+pub mod binary_store;
\ No newline at end of file
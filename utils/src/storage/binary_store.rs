@@ -0,0 +1,255 @@
+use serde::{Deserialize, Serialize};
+
+/// 二进制存储中单条数据的元信息
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoreEntry {
+    pub key: u64,
+    /// 保存时间(毫秒时间戳)
+    pub saved_at_ms: i64,
+    /// 数据大小(字节)
+    pub size: usize,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum StorageError {
+    #[error("IO错误: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("IndexedDB错误: {0}")]
+    IndexedDb(String),
+    #[error("无法获取应用数据目录")]
+    NoDataDir,
+}
+
+pub use imp::BinaryStore;
+
+/// web端使用IndexedDB，数据与元信息分别保存在两个object store中
+#[cfg(target_arch = "wasm32")]
+mod imp {
+    use super::{StorageError, StoreEntry};
+    use crate::time::now_timestamp_ms;
+    use js_sys::{Array, Promise, Uint8Array};
+    use wasm_bindgen::closure::Closure;
+    use wasm_bindgen::{JsCast, JsValue};
+    use wasm_bindgen_futures::JsFuture;
+    use web_sys::{
+        IdbDatabase, IdbObjectStore, IdbOpenDbRequest, IdbRequest, IdbTransactionMode,
+        IdbVersionChangeEvent, window,
+    };
+
+    const DB_VERSION: u32 = 1;
+    const DATA_STORE: &str = "data";
+    const META_STORE: &str = "meta";
+
+    pub struct BinaryStore {
+        db: IdbDatabase,
+    }
+
+    fn js_error(e: JsValue) -> StorageError {
+        StorageError::IndexedDb(format!("{e:?}"))
+    }
+
+    /// 将IndexedDB的请求转换为Future
+    async fn request(request: &IdbRequest) -> Result<JsValue, StorageError> {
+        let promise = Promise::new(&mut |resolve, reject| {
+            let req = request.clone();
+            let on_success = Closure::once_into_js(move |_: web_sys::Event| {
+                let result = req.result().unwrap_or(JsValue::UNDEFINED);
+                let _ = resolve.call1(&JsValue::UNDEFINED, &result);
+            });
+            let req = request.clone();
+            let on_error = Closure::once_into_js(move |_: web_sys::Event| {
+                let error = req
+                    .error()
+                    .ok()
+                    .flatten()
+                    .map(JsValue::from)
+                    .unwrap_or(JsValue::UNDEFINED);
+                let _ = reject.call1(&JsValue::UNDEFINED, &error);
+            });
+            request.set_onsuccess(Some(on_success.unchecked_ref()));
+            request.set_onerror(Some(on_error.unchecked_ref()));
+        });
+        JsFuture::from(promise).await.map_err(js_error)
+    }
+
+    impl BinaryStore {
+        pub async fn open(name: &str) -> Result<Self, StorageError> {
+            let factory = window()
+                .ok_or(StorageError::IndexedDb("无法获取window".to_string()))?
+                .indexed_db()
+                .map_err(js_error)?
+                .ok_or(StorageError::IndexedDb("当前环境不支持IndexedDB".to_string()))?;
+            let open: IdbOpenDbRequest = factory.open_with_u32(name, DB_VERSION).map_err(js_error)?;
+            let on_upgrade = Closure::once_into_js(move |e: IdbVersionChangeEvent| {
+                let Some(target) = e.target() else {
+                    return;
+                };
+                let request: IdbOpenDbRequest = target.unchecked_into();
+                let Ok(db) = request.result() else {
+                    return;
+                };
+                let db: IdbDatabase = db.unchecked_into();
+                for store in [DATA_STORE, META_STORE] {
+                    if !db.object_store_names().contains(store) {
+                        let _ = db.create_object_store(store);
+                    }
+                }
+            });
+            open.set_onupgradeneeded(Some(on_upgrade.unchecked_ref()));
+            let db = request(&open).await?.unchecked_into::<IdbDatabase>();
+            Ok(BinaryStore { db })
+        }
+
+        fn stores(
+            &self,
+            mode: IdbTransactionMode,
+        ) -> Result<(IdbObjectStore, IdbObjectStore), StorageError> {
+            let names = Array::of2(&DATA_STORE.into(), &META_STORE.into());
+            let transaction = self
+                .db
+                .transaction_with_str_sequence_and_mode(&names, mode)
+                .map_err(js_error)?;
+            let data = transaction.object_store(DATA_STORE).map_err(js_error)?;
+            let meta = transaction.object_store(META_STORE).map_err(js_error)?;
+            Ok((data, meta))
+        }
+
+        pub async fn put(&self, key: u64, value: &[u8]) -> Result<(), StorageError> {
+            let (data, meta) = self.stores(IdbTransactionMode::Readwrite)?;
+            let js_key = JsValue::from_f64(key as f64);
+            let entry = StoreEntry {
+                key,
+                saved_at_ms: now_timestamp_ms(),
+                size: value.len(),
+            };
+            let entry = serde_json::to_string(&entry)
+                .map_err(|e| StorageError::IndexedDb(e.to_string()))?;
+            let value = Uint8Array::from(value);
+            let put_data = data.put_with_key(&value, &js_key).map_err(js_error)?;
+            let put_meta = meta
+                .put_with_key(&JsValue::from_str(&entry), &js_key)
+                .map_err(js_error)?;
+            request(&put_data).await?;
+            request(&put_meta).await?;
+            Ok(())
+        }
+
+        pub async fn get(&self, key: u64) -> Result<Option<Vec<u8>>, StorageError> {
+            let (data, _) = self.stores(IdbTransactionMode::Readonly)?;
+            let get = data.get(&JsValue::from_f64(key as f64)).map_err(js_error)?;
+            let value = request(&get).await?;
+            if value.is_undefined() || value.is_null() {
+                return Ok(None);
+            }
+            Ok(Some(Uint8Array::new(&value).to_vec()))
+        }
+
+        pub async fn remove(&self, key: u64) -> Result<(), StorageError> {
+            let (data, meta) = self.stores(IdbTransactionMode::Readwrite)?;
+            let js_key = JsValue::from_f64(key as f64);
+            let delete_data = data.delete(&js_key).map_err(js_error)?;
+            let delete_meta = meta.delete(&js_key).map_err(js_error)?;
+            request(&delete_data).await?;
+            request(&delete_meta).await?;
+            Ok(())
+        }
+
+        pub async fn entries(&self) -> Result<Vec<StoreEntry>, StorageError> {
+            let (_, meta) = self.stores(IdbTransactionMode::Readonly)?;
+            let all = meta.get_all().map_err(js_error)?;
+            let all: Array = request(&all).await?.unchecked_into();
+            let entries = all
+                .iter()
+                .filter_map(|v| v.as_string())
+                .filter_map(|v| serde_json::from_str::<StoreEntry>(&v).ok())
+                .collect();
+            Ok(entries)
+        }
+    }
+}
+
+/// 原生端每条数据保存为应用数据目录下的一个文件，文件读写通过tokio::fs进行，不阻塞UI线程
+#[cfg(not(target_arch = "wasm32"))]
+mod imp {
+    use super::{StorageError, StoreEntry};
+    use std::io::ErrorKind;
+    use std::path::PathBuf;
+    use std::time::UNIX_EPOCH;
+    use tokio::fs;
+
+    const APP_DIR: &str = "block_insight";
+    const EXTENSION: &str = "bin";
+
+    pub struct BinaryStore {
+        dir: PathBuf,
+    }
+
+    impl BinaryStore {
+        pub async fn open(name: &str) -> Result<Self, StorageError> {
+            let dir = dirs::data_dir()
+                .ok_or(StorageError::NoDataDir)?
+                .join(APP_DIR)
+                .join(name);
+            fs::create_dir_all(&dir).await?;
+            Ok(BinaryStore { dir })
+        }
+
+        fn path(&self, key: u64) -> PathBuf {
+            self.dir.join(format!("{key}.{EXTENSION}"))
+        }
+
+        pub async fn put(&self, key: u64, value: &[u8]) -> Result<(), StorageError> {
+            // 先写临时文件再重命名，避免中途退出时留下不完整的数据
+            let tmp = self.dir.join(format!("{key}.tmp"));
+            fs::write(&tmp, value).await?;
+            fs::rename(&tmp, self.path(key)).await?;
+            Ok(())
+        }
+
+        pub async fn get(&self, key: u64) -> Result<Option<Vec<u8>>, StorageError> {
+            match fs::read(self.path(key)).await {
+                Ok(data) => Ok(Some(data)),
+                Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(e.into()),
+            }
+        }
+
+        pub async fn remove(&self, key: u64) -> Result<(), StorageError> {
+            match fs::remove_file(self.path(key)).await {
+                Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+                _ => Ok(()),
+            }
+        }
+
+        pub async fn entries(&self) -> Result<Vec<StoreEntry>, StorageError> {
+            let mut entries = Vec::new();
+            let mut files = fs::read_dir(&self.dir).await?;
+            while let Some(file) = files.next_entry().await? {
+                let path = file.path();
+                if path.extension().and_then(|e| e.to_str()) != Some(EXTENSION) {
+                    continue;
+                }
+                let Some(key) = path
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .and_then(|s| s.parse::<u64>().ok())
+                else {
+                    continue;
+                };
+                let metadata = fs::metadata(&path).await?;
+                let saved_at_ms = metadata
+                    .modified()
+                    .ok()
+                    .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                    .map(|d| d.as_millis() as i64)
+                    .unwrap_or_default();
+                entries.push(StoreEntry {
+                    key,
+                    saved_at_ms,
+                    size: metadata.len() as usize,
+                });
+            }
+            Ok(entries)
+        }
+    }
+}